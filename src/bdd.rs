@@ -0,0 +1,1091 @@
+use std::collections::HashMap;
+
+use crate::node::{assignment::Assignment, clause::Clause, variable::Variable};
+
+// A function in a `Manager`. Equal functions of the same manager are the same handle.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Bdd(u32);
+
+impl Bdd {
+    pub const FALSE: Self = Self(0);
+    pub const TRUE: Self = Self(1);
+
+    pub fn constant(value: bool) -> Self {
+        match value {
+            true => Self::TRUE,
+            false => Self::FALSE,
+        }
+    }
+
+    pub fn is_constant(self) -> bool {
+        self.0 < 2
+    }
+
+    fn index(self) -> usize {
+        self.0 as usize
+    }
+}
+
+// Diagram sizes around a reordering, counted over the roots it was given
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Sifting {
+    before: usize,
+    after: usize,
+    swaps: usize,
+}
+
+impl Sifting {
+    pub fn before(&self) -> usize {
+        self.before
+    }
+
+    pub fn after(&self) -> usize {
+        self.after
+    }
+
+    // Exchanges of adjacent levels it took
+    pub fn swaps(&self) -> usize {
+        self.swaps
+    }
+}
+
+// If `var` then `high` else `low`
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+struct Entry {
+    var: usize,
+    low: Bdd,
+    high: Bdd,
+}
+
+impl Entry {
+    // The terminals, and the slots of freed nodes
+    const VACANT: Self = Self {
+        var: usize::MAX,
+        low: Bdd::FALSE,
+        high: Bdd::FALSE,
+    };
+}
+
+// Reduced ordered binary decision diagrams. No node has equal children and no two
+// nodes have the same variable and children, so every function has one diagram.
+// Variables are tested in the order they were first asked for, until reordered.
+// Reordering rewrites nodes in place, so handles keep standing for their function.
+// Garbage collection, which sifting starts with, frees the nodes that neither its
+// roots nor a protected handle reach. A handle to a freed node must not be used
+// again: most methods panic on one, but its slot may since stand for another
+// function. With automatic reordering, every handle an operation returns is
+// protected, so it stays valid until released.
+pub struct Manager {
+    variables: Vec<Variable>,
+    indices: HashMap<Variable, usize>,
+    // Variable to level and back
+    level: Vec<usize>,
+    order: Vec<usize>,
+    nodes: Vec<Entry>,
+    // Per node, its parents and protections
+    refs: Vec<usize>,
+    // Per node, its position in `by_var`
+    slots: Vec<usize>,
+    // Freed nodes, reused first
+    free: Vec<u32>,
+    // The nodes testing each variable, so the size of each level
+    by_var: Vec<Vec<Bdd>>,
+    // Nodes not freed, the terminals left out
+    live: usize,
+    protected: HashMap<Bdd, usize>,
+    unique: HashMap<Entry, Bdd>,
+    cache: HashMap<(Bdd, Bdd, Bdd), Bdd>,
+    threshold: usize,
+    auto: bool,
+    // Operations under way, automatic reordering waits for the outermost
+    depth: usize,
+}
+
+impl Default for Manager {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Manager {
+    pub fn new() -> Self {
+        Self {
+            variables: Vec::new(),
+            indices: HashMap::new(),
+            level: Vec::new(),
+            order: Vec::new(),
+            nodes: vec![Entry::VACANT; 2],
+            refs: vec![0; 2],
+            slots: vec![0; 2],
+            free: Vec::new(),
+            by_var: Vec::new(),
+            live: 0,
+            protected: HashMap::new(),
+            unique: HashMap::new(),
+            cache: HashMap::new(),
+            threshold: 4096,
+            auto: false,
+            depth: 0,
+        }
+    }
+
+    // Variables tested in the given order, from the top down
+    pub fn with_order(order: &[Variable]) -> Self {
+        let mut manager = Self::new();
+
+        manager.set_order(order);
+        manager
+    }
+
+    // The function that is just `variable`, placed below every known variable if new
+    pub fn variable(&mut self, variable: Variable) -> Bdd {
+        let var = self.declare(variable);
+
+        self.operation(|manager| manager.make(var, Bdd::FALSE, Bdd::TRUE))
+    }
+
+    // Place `variable` below every known variable if new, without building anything
+    pub fn declare(&mut self, variable: Variable) -> usize {
+        *self.indices.entry(variable).or_insert_with(|| {
+            self.variables.push(variable);
+            self.level.push(self.order.len());
+            self.order.push(self.variables.len() - 1);
+            self.by_var.push(Vec::new());
+            self.variables.len() - 1
+        })
+    }
+
+    // From the top of the diagrams down
+    pub fn variables(&self) -> Vec<Variable> {
+        self.order.iter().map(|&var| self.variables[var]).collect()
+    }
+
+    // The variable tested by a node and its children for false and true
+    pub fn node(&self, f: Bdd) -> Option<(Variable, Bdd, Bdd)> {
+        let entry = self.entry(f);
+
+        match f.is_constant() {
+            true => None,
+            false => Some((self.variables[entry.var], entry.low, entry.high)),
+        }
+    }
+
+    // Every node the function's diagram reaches, the terminals included
+    pub fn size(&self, f: Bdd) -> usize {
+        self.shared_size(&[f])
+    }
+
+    // The nodes of several diagrams, those they share counted once
+    pub fn shared_size(&self, roots: &[Bdd]) -> usize {
+        let mut seen = vec![false; self.nodes.len()];
+        let mut stack = roots.to_vec();
+        let mut size = 0;
+
+        while let Some(f) = stack.pop() {
+            if std::mem::replace(&mut seen[f.index()], true) {
+                continue;
+            }
+
+            size += 1;
+
+            if !f.is_constant() {
+                let entry = self.entry(f);
+
+                stack.extend([entry.low, entry.high]);
+            }
+        }
+
+        size
+    }
+
+    // Move the listed variables to the top in that order, declaring new ones.
+    // The others keep their order below them.
+    pub fn set_order(&mut self, order: &[Variable]) {
+        for (target, &v) in order.iter().enumerate() {
+            let var = self.declare(v);
+
+            while self.level[var] > target {
+                self.swap(self.level[var] - 1, false);
+            }
+        }
+    }
+
+    // Rudell's sifting: every variable, the most used first, is moved through all
+    // levels and left where the fewest nodes were live. Garbage is collected first,
+    // so those are the nodes of the roots' and the protected handles' diagrams.
+    pub fn sift(&mut self, roots: &[Bdd]) -> Sifting {
+        self.collect_garbage(roots);
+
+        // Held for the swaps, so a root some swapped node stops using stays
+        for &f in roots {
+            self.reference(f);
+        }
+
+        let before = self.shared_size(roots);
+        let mut swaps = 0;
+        let mut vars: Vec<usize> = (0..self.variables.len()).collect();
+
+        vars.sort_by_key(|&var| std::cmp::Reverse(self.by_var[var].len()));
+
+        for var in vars {
+            let mut best = (self.live, self.level[var]);
+
+            while self.level[var] + 1 < self.order.len() {
+                self.swap(self.level[var], true);
+                swaps += 1;
+                best = best.min((self.live, self.level[var]));
+            }
+
+            while self.level[var] > 0 {
+                self.swap(self.level[var] - 1, true);
+                swaps += 1;
+                best = best.min((self.live, self.level[var]));
+            }
+
+            while self.level[var] < best.1 {
+                self.swap(self.level[var], true);
+                swaps += 1;
+            }
+        }
+
+        for &f in roots {
+            if !f.is_constant() {
+                self.refs[f.index()] -= 1;
+            }
+        }
+
+        // It may name nodes the swaps freed
+        self.cache.clear();
+
+        let after = self.shared_size(roots);
+
+        self.threshold = self.threshold.max(2 * after);
+
+        Sifting {
+            before,
+            after,
+            swaps,
+        }
+    }
+
+    // Sift once the roots' diagrams outgrow the threshold, which then doubles
+    // past the size sifting got them to
+    pub fn sift_if_grown(&mut self, roots: &[Bdd]) -> Option<Sifting> {
+        match self.shared_size(roots) > self.threshold {
+            true => Some(self.sift(roots)),
+            false => None,
+        }
+    }
+
+    pub fn set_threshold(&mut self, threshold: usize) {
+        self.threshold = threshold;
+    }
+
+    // Sift after any operation that leaves more live nodes than the threshold. The
+    // handles operations return from then on are protected, and need releasing
+    // for their nodes to be freed.
+    pub fn set_auto_reorder(&mut self, on: bool) {
+        self.auto = on;
+    }
+
+    // Runs the operations in `op` as one, so automatic reordering waits until it
+    // returns and the handles it holds meanwhile stay valid
+    pub fn operation(&mut self, op: impl FnOnce(&mut Self) -> Bdd) -> Bdd {
+        self.depth += 1;
+
+        let r = op(self);
+
+        self.depth -= 1;
+
+        if self.depth == 0 && self.auto {
+            self.protect(r);
+
+            if self.live > self.threshold {
+                self.sift(&[r]);
+            }
+        }
+
+        r
+    }
+
+    // Keep f through garbage collection and reordering, until released as often
+    pub fn protect(&mut self, f: Bdd) {
+        if !f.is_constant() {
+            *self.protected.entry(f).or_default() += 1;
+            self.refs[f.index()] += 1;
+        }
+    }
+
+    pub fn release(&mut self, f: Bdd) {
+        if f.is_constant() {
+            return;
+        }
+
+        let count = self
+            .protected
+            .get_mut(&f)
+            .expect("release of an unprotected handle");
+
+        *count -= 1;
+
+        if *count == 0 {
+            self.protected.remove(&f);
+        }
+
+        self.refs[f.index()] -= 1;
+    }
+
+    // Free every node that neither `roots` nor a protected handle reaches, and
+    // say how many. Handles to freed nodes are no longer valid.
+    pub fn collect_garbage(&mut self, roots: &[Bdd]) -> usize {
+        let mut reached = vec![false; self.nodes.len()];
+        let mut stack: Vec<Bdd> = roots.iter().chain(self.protected.keys()).copied().collect();
+
+        while let Some(f) = stack.pop() {
+            if f.is_constant() || std::mem::replace(&mut reached[f.index()], true) {
+                continue;
+            }
+
+            let entry = self.entry(f);
+
+            stack.extend([entry.low, entry.high]);
+        }
+
+        let dead: Vec<Bdd> = (2..self.nodes.len())
+            .map(|i| Bdd(i as u32))
+            .filter(|&f| !reached[f.index()] && self.nodes[f.index()] != Entry::VACANT)
+            .collect();
+
+        for &f in &dead {
+            self.vacate(f);
+        }
+
+        // Count the parents again, the freed ones gone
+        self.refs.fill(0);
+
+        for f in (2..self.nodes.len()).filter(|&i| reached[i]) {
+            let entry = self.nodes[f];
+
+            self.reference(entry.low);
+            self.reference(entry.high);
+        }
+
+        for (&f, &count) in &self.protected {
+            self.refs[f.index()] += count;
+        }
+
+        self.cache.clear();
+        dead.len()
+    }
+
+    // Nodes in use or not yet collected, the terminals left out
+    pub fn live(&self) -> usize {
+        self.live
+    }
+
+    // If f then g else h, which every operator reduces to
+    pub fn ite(&mut self, f: Bdd, g: Bdd, h: Bdd) -> Bdd {
+        self.operation(|manager| manager.ite_below(f, g, h))
+    }
+
+    fn ite_below(&mut self, f: Bdd, g: Bdd, h: Bdd) -> Bdd {
+        match (f, g, h) {
+            (Bdd::TRUE, _, _) => return g,
+            (Bdd::FALSE, _, _) => return h,
+            _ if g == h => return g,
+            (_, Bdd::TRUE, Bdd::FALSE) => return f,
+            _ => (),
+        }
+
+        if let Some(&r) = self.cache.get(&(f, g, h)) {
+            return r;
+        }
+
+        let level = [f, g, h]
+            .map(|f| self.level_of(f))
+            .into_iter()
+            .min()
+            .unwrap();
+        let var = self.order[level];
+        let (fl, fh) = self.cofactors(f, var);
+        let (gl, gh) = self.cofactors(g, var);
+        let (hl, hh) = self.cofactors(h, var);
+        let low = self.ite_below(fl, gl, hl);
+        let high = self.ite_below(fh, gh, hh);
+        let r = self.make(var, low, high);
+
+        self.cache.insert((f, g, h), r);
+        r
+    }
+
+    pub fn not(&mut self, f: Bdd) -> Bdd {
+        self.ite(f, Bdd::FALSE, Bdd::TRUE)
+    }
+
+    pub fn and(&mut self, f: Bdd, g: Bdd) -> Bdd {
+        self.ite(f, g, Bdd::FALSE)
+    }
+
+    pub fn or(&mut self, f: Bdd, g: Bdd) -> Bdd {
+        self.ite(f, Bdd::TRUE, g)
+    }
+
+    // Any binary operator of a formula
+    pub fn apply(&mut self, operator: Clause, f: Bdd, g: Bdd) -> Bdd {
+        self.operation(|manager| match operator {
+            Clause::Conjunction => manager.and(f, g),
+            Clause::Disjunction => manager.or(f, g),
+            Clause::Exclusive => {
+                let not = manager.not(g);
+
+                manager.ite(f, not, g)
+            }
+            Clause::Material => manager.ite(f, g, Bdd::TRUE),
+            Clause::Equivalence => {
+                let not = manager.not(g);
+
+                manager.ite(f, g, not)
+            }
+            _ => panic!("{operator:?} is not a binary operator"),
+        })
+    }
+
+    // f with `variable` fixed to `value`
+    pub fn restrict(&mut self, f: Bdd, variable: Variable, value: bool) -> Bdd {
+        match self.indices.get(&variable) {
+            Some(&var) => {
+                self.operation(|manager| manager.restrict_var(f, var, value, &mut HashMap::new()))
+            }
+            None => f,
+        }
+    }
+
+    // f with `g` in place of `variable`
+    pub fn compose(&mut self, f: Bdd, variable: Variable, g: Bdd) -> Bdd {
+        self.operation(|manager| {
+            let high = manager.restrict(f, variable, true);
+            let low = manager.restrict(f, variable, false);
+
+            manager.ite(g, high, low)
+        })
+    }
+
+    // ∃x f ⇔ f[x := 0] ∨ f[x := 1]
+    pub fn exists(&mut self, f: Bdd, variables: &[Variable]) -> Bdd {
+        self.operation(|manager| {
+            variables.iter().fold(f, |f, &v| {
+                let low = manager.restrict(f, v, false);
+                let high = manager.restrict(f, v, true);
+
+                manager.or(low, high)
+            })
+        })
+    }
+
+    // ∀x f ⇔ f[x := 0] ∧ f[x := 1]
+    pub fn forall(&mut self, f: Bdd, variables: &[Variable]) -> Bdd {
+        self.operation(|manager| {
+            variables.iter().fold(f, |f, &v| {
+                let low = manager.restrict(f, v, false);
+                let high = manager.restrict(f, v, true);
+
+                manager.and(low, high)
+            })
+        })
+    }
+
+    // Satisfying assignments of every variable the manager knows, None past u128
+    pub fn count(&self, f: Bdd) -> Option<u128> {
+        let mut memo = HashMap::new();
+
+        scale(self.count_below(f, &mut memo)?, self.level_of(f))
+    }
+
+    // One partial assignment per path to true. Variables off the path may take either value.
+    pub fn paths(&self, f: Bdd) -> Vec<Assignment> {
+        let mut paths = Vec::new();
+
+        self.collect_paths(f, &mut Assignment::new(), &mut paths);
+        paths
+    }
+
+    // The path to true that prefers false, every other variable false as well
+    pub fn any_sat(&self, f: Bdd) -> Option<Assignment> {
+        let mut assignment: Assignment = self.variables.iter().map(|&v| (v, false)).collect();
+        let mut f = f;
+
+        while !f.is_constant() {
+            let entry = self.entry(f);
+
+            f = match entry.low {
+                Bdd::FALSE => {
+                    assignment.set(self.variables[entry.var], true);
+                    entry.high
+                }
+                low => low,
+            };
+        }
+
+        (f == Bdd::TRUE).then_some(assignment)
+    }
+
+    fn make(&mut self, var: usize, low: Bdd, high: Bdd) -> Bdd {
+        if low == high {
+            return low;
+        }
+
+        let entry = Entry { var, low, high };
+
+        if let Some(&f) = self.unique.get(&entry) {
+            return f;
+        }
+
+        let f = match self.free.pop() {
+            Some(index) => Bdd(index),
+            None => {
+                self.nodes.push(Entry::VACANT);
+                self.refs.push(0);
+                self.slots.push(0);
+                Bdd(self.nodes.len() as u32 - 1)
+            }
+        };
+
+        self.nodes[f.index()] = entry;
+        self.refs[f.index()] = 0;
+        self.unique.insert(entry, f);
+        self.attach(f);
+        self.reference(low);
+        self.reference(high);
+        self.live += 1;
+        f
+    }
+
+    fn reference(&mut self, f: Bdd) {
+        if !f.is_constant() {
+            self.refs[f.index()] += 1;
+        }
+    }
+
+    // Drop a reference, freeing the node once none are left, and with it what only
+    // it kept alive
+    fn dereference(&mut self, f: Bdd) {
+        if f.is_constant() {
+            return;
+        }
+
+        self.refs[f.index()] -= 1;
+
+        if self.refs[f.index()] == 0 {
+            let entry = self.nodes[f.index()];
+
+            self.vacate(f);
+            self.dereference(entry.low);
+            self.dereference(entry.high);
+        }
+    }
+
+    // Free the node's slot, leaving its children's references alone
+    fn vacate(&mut self, f: Bdd) {
+        let entry = self.nodes[f.index()];
+
+        self.detach(f);
+        self.unique.remove(&entry);
+        self.nodes[f.index()] = Entry::VACANT;
+        self.free.push(f.0);
+        self.live -= 1;
+    }
+
+    // Add the node to its variable's level
+    fn attach(&mut self, f: Bdd) {
+        let level = &mut self.by_var[self.nodes[f.index()].var];
+
+        self.slots[f.index()] = level.len();
+        level.push(f);
+    }
+
+    fn detach(&mut self, f: Bdd) {
+        let slot = self.slots[f.index()];
+        let level = &mut self.by_var[self.nodes[f.index()].var];
+
+        level.swap_remove(slot);
+
+        if let Some(&moved) = level.get(slot) {
+            self.slots[moved.index()] = slot;
+        }
+    }
+
+    // Exchange the variables at `level` and the one below. A node of the upper variable
+    // depending on the lower one is rewritten to test the lower one first, into nodes
+    // of the upper one; the rest just move down a level with their variable.
+    // With `reclaim`, the old nodes of the lower variable that lose their last
+    // parent are freed, which is only safe once every handle is counted.
+    fn swap(&mut self, level: usize, reclaim: bool) {
+        let (x, y) = (self.order[level], self.order[level + 1]);
+
+        for f in self.by_var[x].clone() {
+            let entry = self.nodes[f.index()];
+            let (f0, f1) = (entry.low, entry.high);
+
+            if [f0, f1]
+                .iter()
+                .all(|g| g.is_constant() || self.nodes[g.index()].var != y)
+            {
+                continue;
+            }
+
+            let (f00, f01) = self.cofactors(f0, y);
+            let (f10, f11) = self.cofactors(f1, y);
+            let low = self.make(x, f00, f10);
+            let high = self.make(x, f01, f11);
+            let swapped = Entry { var: y, low, high };
+
+            self.reference(low);
+            self.reference(high);
+            self.detach(f);
+            self.unique.remove(&entry);
+            self.unique.insert(swapped, f);
+            self.nodes[f.index()] = swapped;
+            self.attach(f);
+
+            for g in [f0, f1] {
+                match reclaim {
+                    true => self.dereference(g),
+                    false if !g.is_constant() => self.refs[g.index()] -= 1,
+                    false => {}
+                }
+            }
+        }
+
+        self.order.swap(level, level + 1);
+        self.level[x] = level + 1;
+        self.level[y] = level;
+    }
+
+    // What a node tests, checked not to be freed
+    fn entry(&self, f: Bdd) -> Entry {
+        let entry = self.nodes[f.index()];
+
+        assert!(
+            f.is_constant() || entry != Entry::VACANT,
+            "{f:?} names a freed node, protect handles to keep them"
+        );
+        entry
+    }
+
+    fn level_of(&self, f: Bdd) -> usize {
+        match f.is_constant() {
+            true => self.order.len(),
+            false => self.level[self.entry(f).var],
+        }
+    }
+
+    fn cofactors(&self, f: Bdd, var: usize) -> (Bdd, Bdd) {
+        let entry = self.entry(f);
+
+        match !f.is_constant() && entry.var == var {
+            true => (entry.low, entry.high),
+            false => (f, f),
+        }
+    }
+
+    fn restrict_var(
+        &mut self,
+        f: Bdd,
+        var: usize,
+        value: bool,
+        memo: &mut HashMap<Bdd, Bdd>,
+    ) -> Bdd {
+        // Below the variable's level it cannot occur
+        if self.level_of(f) > self.level[var] {
+            return f;
+        }
+
+        if let Some(&r) = memo.get(&f) {
+            return r;
+        }
+
+        let entry = self.entry(f);
+        let r = match entry.var == var {
+            true if value => entry.high,
+            true => entry.low,
+            false => {
+                let low = self.restrict_var(entry.low, var, value, memo);
+                let high = self.restrict_var(entry.high, var, value, memo);
+
+                self.make(entry.var, low, high)
+            }
+        };
+
+        memo.insert(f, r);
+        r
+    }
+
+    // Over the variables from f's level down
+    fn count_below(&self, f: Bdd, memo: &mut HashMap<Bdd, u128>) -> Option<u128> {
+        if f.is_constant() {
+            return Some((f == Bdd::TRUE) as u128);
+        }
+
+        if let Some(&count) = memo.get(&f) {
+            return Some(count);
+        }
+
+        let entry = self.entry(f);
+        let level = self.level[entry.var];
+        let mut count: u128 = 0;
+
+        for child in [entry.low, entry.high] {
+            let below = self.count_below(child, memo)?;
+
+            count = count.checked_add(scale(below, self.level_of(child) - level - 1)?)?;
+        }
+
+        memo.insert(f, count);
+        Some(count)
+    }
+
+    fn collect_paths(&self, f: Bdd, path: &mut Assignment, paths: &mut Vec<Assignment>) {
+        match f {
+            Bdd::FALSE => (),
+            Bdd::TRUE => paths.push(path.clone()),
+            _ => {
+                let entry = self.entry(f);
+                let variable = self.variables[entry.var];
+
+                for (child, value) in [(entry.low, false), (entry.high, true)] {
+                    let mut path = path.clone();
+
+                    path.set(variable, value);
+                    self.collect_paths(child, &mut path, paths);
+                }
+            }
+        }
+    }
+}
+
+// n · 2^shift, None if that does not fit
+fn scale(n: u128, shift: usize) -> Option<u128> {
+    match n {
+        0 => Some(0),
+        _ if shift <= n.leading_zeros() as usize => Some(n << shift),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::node::{Node, bdd::Heuristic};
+
+    #[test]
+    fn canonical() {
+        let mut manager = Manager::new();
+        let a = build(&mut manager, "AB&!");
+        let b = build(&mut manager, "A!B!|");
+
+        assert_eq!(a, b);
+        assert_eq!(build(&mut manager, "AA!|"), Bdd::TRUE);
+        assert_eq!(build(&mut manager, "AA!&"), Bdd::FALSE);
+        assert_eq!(build(&mut manager, "AB>"), build(&mut manager, "B!A!>"));
+        assert_ne!(build(&mut manager, "AB>"), build(&mut manager, "BA>"));
+    }
+
+    #[test]
+    fn count() {
+        for (formula, count) in [
+            ("AB|", 3),
+            ("AB&", 1),
+            ("AB^", 2),
+            ("AB=C|", 6),
+            ("AA!&", 0),
+            ("1", 1),
+        ] {
+            let mut manager = Manager::new();
+            let f = build(&mut manager, formula);
+
+            assert_eq!(manager.count(f), Some(count));
+        }
+
+        // Over every variable the manager knows
+        let mut manager = Manager::new();
+        let f = build(&mut manager, "AB|");
+
+        build(&mut manager, "C");
+        assert_eq!(manager.count(f), Some(6));
+
+        // 2^127 fits, 2^128 does not
+        let names: Vec<String> = (0..129).map(|i| format!("x{i}")).collect();
+        let mut manager = Manager::new();
+        let all = Node::from_infix(&names.join(" & "))
+            .unwrap()
+            .to_bdd(&mut manager);
+        let first = manager.variable(Variable::new("x0"));
+        let pair = build(&mut manager, "{x0}{x1}&");
+
+        assert_eq!(manager.count(all), Some(1));
+        assert_eq!(manager.count(pair), Some(1 << 127));
+        assert_eq!(manager.count(first), None);
+    }
+
+    #[test]
+    fn quantify() {
+        let mut manager = Manager::new();
+        let f = build(&mut manager, "AB&C|");
+        let [a, b, c] = ['A', 'B', 'C'].map(Variable::from);
+
+        let restricted = manager.restrict(f, c, false);
+        assert_eq!(restricted, build(&mut manager, "AB&"));
+
+        let restricted = manager.restrict(f, a, true);
+        assert_eq!(restricted, build(&mut manager, "BC|"));
+
+        let exists = manager.exists(f, &[a]);
+        assert_eq!(exists, build(&mut manager, "BC|"));
+
+        let forall = manager.forall(f, &[a]);
+        assert_eq!(forall, build(&mut manager, "C"));
+
+        let g = build(&mut manager, "B!");
+        let composed = manager.compose(f, a, g);
+        assert_eq!(composed, build(&mut manager, "C"));
+
+        let composed = manager.compose(f, c, g);
+        assert_eq!(composed, build(&mut manager, "AB!|"));
+
+        let exists = manager.exists(f, &[a, b, c]);
+        assert_eq!(exists, Bdd::TRUE);
+
+        let forall = manager.forall(f, &[b]);
+        assert_eq!(forall, build(&mut manager, "C"));
+    }
+
+    #[test]
+    fn paths() {
+        let mut manager = Manager::new();
+        let node: Node = "AB^C&".parse().unwrap();
+        let f = node.to_bdd(&mut manager);
+        let paths = manager.paths(f);
+
+        assert_eq!(
+            paths.iter().map(Assignment::to_string).collect::<Vec<_>>(),
+            ["A=0 B=1 C=1", "A=1 B=0 C=1"]
+        );
+
+        for path in paths {
+            assert_eq!(node.evaluate_under(&path), Ok(true));
+        }
+
+        let model = manager.any_sat(f).unwrap();
+
+        assert_eq!(model.to_string(), "A=0 B=1 C=1");
+        assert!(manager.any_sat(Bdd::FALSE).is_none());
+    }
+
+    #[test]
+    fn roundtrip() {
+        let mut manager = Manager::new();
+
+        for formula in [
+            "AB&C|",
+            "AB^C^D^",
+            "AB>C=",
+            "AB|C&D!|",
+            "AB!&C|C!&",
+            "1",
+            "0",
+        ] {
+            let node: Node = formula.parse().unwrap();
+            let f = node.to_bdd(&mut manager);
+            let back = Node::from_bdd(&manager, f);
+
+            println!("{node} {back}");
+
+            assert_eq!(back.equivalent(&node), Ok(()));
+            assert_eq!(back.to_bdd(&mut manager), f);
+        }
+    }
+
+    // Linear in the number of variables, where the truth table doubles
+    #[test]
+    fn xor_chain() {
+        let mut manager = Manager::new();
+        let names: Vec<String> = (0..64).map(|i| format!("x{i:02}")).collect();
+        let node = Node::from_infix(&names.join(" ^ ")).unwrap();
+        let f = node.to_bdd(&mut manager);
+
+        assert_eq!(manager.size(f), 2 * 64 + 1);
+        assert_eq!(manager.count(f), Some(1 << 63));
+
+        let reversed: Vec<String> = names.into_iter().rev().collect();
+        let other = Node::from_infix(&reversed.join(" ^ ")).unwrap();
+
+        assert_eq!(other.to_bdd(&mut manager), f);
+    }
+
+    // (a0 ∧ b0) ∨ (a1 ∧ b1) ∨ ...: exponential with every a above every b, linear interleaved
+    fn pairs(n: usize) -> Node {
+        let terms: Vec<String> = (0..n).map(|i| format!("a{i} & b{i}")).collect();
+
+        Node::from_infix(&terms.join(" | ")).unwrap()
+    }
+
+    #[test]
+    fn order() {
+        let node = pairs(6);
+        let mut alphabetical = Manager::new();
+        let f = node.to_bdd(&mut alphabetical);
+
+        assert_eq!(alphabetical.size(f), 1 << (6 + 1));
+
+        let order = node.variable_order(Heuristic::DepthFirst);
+        let mut interleaved = Manager::with_order(&order);
+        let g = node.to_bdd(&mut interleaved);
+
+        assert_eq!(interleaved.variables(), order);
+        assert_eq!(interleaved.size(g), 2 * 6 + 2);
+
+        // Reordering a manager in place gives the diagram it would have been built with
+        alphabetical.set_order(&order);
+
+        assert_eq!(alphabetical.variables(), order);
+        assert_eq!(alphabetical.size(f), 2 * 6 + 2);
+        assert_eq!(Node::from_bdd(&alphabetical, f).equivalent(&node), Ok(()));
+    }
+
+    #[test]
+    fn sift() {
+        let node = pairs(6);
+        let mut manager = Manager::new();
+        let f = node.to_bdd(&mut manager);
+        let g = build(&mut manager, "{a0}{b5}^");
+        let count = (manager.count(f), manager.count(g));
+        let sifting = manager.sift(&[f, g]);
+
+        println!("{sifting:?}");
+
+        assert!(sifting.after() < sifting.before());
+        assert!(manager.size(f) <= 2 * 6 + 2);
+
+        // Handles still stand for the same functions, and stay canonical
+        assert_eq!((manager.count(f), manager.count(g)), count);
+        assert_eq!(Node::from_bdd(&manager, f).equivalent(&node), Ok(()));
+        assert_eq!(node.to_bdd(&mut manager), f);
+        assert_eq!(build(&mut manager, "{b5}{a0}^"), g);
+    }
+
+    #[test]
+    fn threshold() {
+        let node = pairs(5);
+        let mut manager = Manager::new();
+        let f = node.to_bdd(&mut manager);
+
+        assert_eq!(manager.sift_if_grown(&[f]), None);
+
+        manager.set_threshold(manager.size(f) - 1);
+
+        let sifting = manager.sift_if_grown(&[f]).unwrap();
+
+        assert_eq!(sifting.after(), manager.size(f));
+        assert_eq!(manager.sift_if_grown(&[f]), None);
+    }
+
+    #[test]
+    fn garbage() {
+        let mut manager = Manager::new();
+        let f = build(&mut manager, "{a0}{b0}&");
+        let g = pairs(5).to_bdd(&mut manager);
+
+        // g is protected, the intermediate diagrams are not
+        manager.protect(g);
+        manager.collect_garbage(&[f]);
+
+        assert_eq!(manager.live(), manager.shared_size(&[f, g]) - 2);
+
+        // Without g, only f's nodes are left, and they are shared again when rebuilt
+        manager.release(g);
+
+        assert!(manager.collect_garbage(&[f]) > 0);
+        assert_eq!(manager.live(), manager.size(f) - 2);
+        assert_eq!(build(&mut manager, "{a0}{b0}&"), f);
+
+        // Sifting counts the live nodes as it goes, none left behind
+        let h = pairs(6).to_bdd(&mut manager);
+
+        build(&mut manager, "{a1}{b4}|");
+
+        let sifting = manager.sift(&[f, h]);
+
+        assert!(sifting.after() < sifting.before());
+        assert_eq!(manager.live(), sifting.after() - 2);
+        assert_eq!(manager.count(f), Some(1 << 10));
+    }
+
+    #[test]
+    fn auto_reorder() {
+        let node = pairs(6);
+        let mut manager = Manager::new();
+
+        manager.set_auto_reorder(true);
+        manager.set_threshold(16);
+
+        let f = node.to_bdd(&mut manager);
+
+        assert!(manager.size(f) <= 2 * 6 + 2);
+        assert_eq!(manager.live(), manager.size(f) - 2);
+        assert_eq!(Node::from_bdd(&manager, f).equivalent(&node), Ok(()));
+
+        // Handles returned earlier survive the reorderings after later operations
+        manager.set_threshold(0);
+
+        let a = build(&mut manager, "AB&CD&|");
+        let g = build(&mut manager, "{a5}{b0}^");
+        let h = manager.or(f, g);
+        let either = node.join(Clause::Disjunction, &"{a5}{b0}^".parse().unwrap());
+
+        assert_eq!(manager.live(), manager.shared_size(&[f, a, g, h]) - 2);
+        assert_eq!(
+            Node::from_bdd(&manager, a).equivalent(&"AB&CD&|".parse().unwrap()),
+            Ok(())
+        );
+        assert_eq!(Node::from_bdd(&manager, f).equivalent(&node), Ok(()));
+        assert_eq!(Node::from_bdd(&manager, h).equivalent(&either), Ok(()));
+
+        // Released, they are freed
+        for r in [f, a, g, h] {
+            manager.release(r);
+        }
+
+        manager.collect_garbage(&[]);
+
+        assert_eq!(manager.live(), 0);
+    }
+
+    #[test]
+    #[should_panic(expected = "names a freed node")]
+    fn freed() {
+        let mut manager = Manager::new();
+        let f = build(&mut manager, "AB&");
+
+        manager.collect_garbage(&[]);
+        manager.count(f);
+    }
+
+    #[test]
+    fn heuristics() {
+        let node = Node::from_infix("c & (b | a & d) | c & e").unwrap();
+        let names = |heuristic| {
+            node.variable_order(heuristic)
+                .iter()
+                .map(|v| v.name())
+                .collect::<Vec<_>>()
+        };
+
+        assert_eq!(names(Heuristic::Alphabetical), ["a", "b", "c", "d", "e"]);
+        assert_eq!(names(Heuristic::DepthFirst), ["c", "b", "a", "d", "e"]);
+        assert_eq!(names(Heuristic::Weight), ["c", "e", "b", "a", "d"]);
+    }
+
+    fn build(manager: &mut Manager, formula: &str) -> Bdd {
+        formula.parse::<Node>().unwrap().to_bdd(manager)
+    }
+}
@@ -1,5 +1,3 @@
-use std::mem;
-
 // Z-order curve
 pub fn map(x: u16, y: u16) -> f64 {
     let mut z: u64 = 0;
@@ -13,11 +11,11 @@ pub fn map(x: u16, y: u16) -> f64 {
     }
 
     // f64's mantissa is 52 bits, plenty to fit our two interleaved u16's
-    unsafe { mem::transmute(z) }
+    f64::from_bits(z)
 }
 
 pub fn reverse_map(n: f64) -> (u16, u16) {
-    let z: u64 = unsafe { mem::transmute(n) };
+    let z: u64 = n.to_bits();
     let mut x = 0;
     let mut y = 0;
 
@@ -0,0 +1,364 @@
+use std::{collections::HashSet, fmt::Display};
+
+use crate::{
+    node::{Node, variable::Variable},
+    solver::{Cnf, Literal},
+};
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum DimacsErrorKind {
+    MissingHeader,
+    InvalidHeader,
+    InvalidLiteral,
+    VariableOutOfRange(usize),
+    DuplicateName(Variable),
+    ClauseCount { expected: usize, found: usize },
+    UnterminatedClause,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct DimacsError {
+    kind: DimacsErrorKind,
+    line: usize,
+}
+
+impl DimacsError {
+    fn new(kind: DimacsErrorKind, line: usize) -> Self {
+        Self { kind, line }
+    }
+
+    pub fn kind(&self) -> DimacsErrorKind {
+        self.kind
+    }
+
+    // Counting from 1
+    pub fn line(&self) -> usize {
+        self.line
+    }
+}
+
+// `p cnf` files, with names for variables read from `c var <n> <name>` comments.
+// Variables without one are called x<n>, with underscores appended while that
+// name is taken. Names go up to the last variable used or named, so a header
+// declaring more than that costs nothing.
+pub fn read(input: &str) -> Result<(Cnf, Vec<Variable>), DimacsError> {
+    let mut header: Option<(usize, usize)> = None;
+    // Checked against the header once it has been read
+    let mut declared: Vec<(usize, Variable, usize)> = Vec::new();
+    let mut clauses: Vec<Vec<Literal>> = Vec::new();
+    let mut clause: Vec<Literal> = Vec::new();
+    // The last variable a clause holds
+    let mut used = 0;
+    let mut last = 0;
+
+    for (line, text) in input
+        .lines()
+        .enumerate()
+        .map(|(i, text)| (i + 1, text.trim()))
+    {
+        let mut words = text.split_whitespace();
+
+        last = line;
+
+        match words.next() {
+            None => continue,
+            Some("c") => {
+                if let (Some("var"), Some(n), Some(name), None) =
+                    (words.next(), words.next(), words.next(), words.next())
+                    && let Ok(n @ 1..) = n.parse::<usize>()
+                    && Variable::is_identifier(name)
+                {
+                    declared.push((n, Variable::new(name), line));
+                }
+
+                continue;
+            }
+            Some("p") => {
+                let (Some("cnf"), Some(variables), Some(count), None) =
+                    (words.next(), words.next(), words.next(), words.next())
+                else {
+                    return Err(DimacsError::new(DimacsErrorKind::InvalidHeader, line));
+                };
+
+                match (variables.parse(), count.parse(), header) {
+                    (Ok(variables @ ..Literal::VARIABLES), Ok(count), None) => {
+                        header = Some((variables, count))
+                    }
+                    _ => return Err(DimacsError::new(DimacsErrorKind::InvalidHeader, line)),
+                }
+
+                continue;
+            }
+            // Trailer of the SATLIB benchmarks
+            Some("%") => break,
+            Some(_) => (),
+        }
+
+        let Some((variables, _)) = header else {
+            return Err(DimacsError::new(DimacsErrorKind::MissingHeader, line));
+        };
+
+        for word in text.split_whitespace() {
+            let n: i64 = word
+                .parse()
+                .map_err(|_| DimacsError::new(DimacsErrorKind::InvalidLiteral, line))?;
+            let v = n.unsigned_abs() as usize;
+            let out_of_range = DimacsError::new(DimacsErrorKind::VariableOutOfRange(v), line);
+
+            match n {
+                0 => clauses.push(std::mem::take(&mut clause)),
+                _ if v > variables => return Err(out_of_range),
+                _ => {
+                    clause.push(Literal::try_new(v - 1, n > 0).ok_or(out_of_range)?);
+                    used = used.max(v);
+                }
+            }
+        }
+    }
+
+    let Some((variables, count)) = header else {
+        return Err(DimacsError::new(DimacsErrorKind::MissingHeader, last));
+    };
+
+    if !clause.is_empty() {
+        return Err(DimacsError::new(DimacsErrorKind::UnterminatedClause, last));
+    }
+
+    if clauses.len() != count {
+        return Err(DimacsError::new(
+            DimacsErrorKind::ClauseCount {
+                expected: count,
+                found: clauses.len(),
+            },
+            last,
+        ));
+    }
+
+    let mut cnf = Cnf::new(variables);
+
+    for clause in clauses {
+        cnf.add_clause(clause);
+    }
+
+    if let Some(&(n, _, line)) = declared.iter().find(|&&(n, _, _)| n > variables) {
+        return Err(DimacsError::new(
+            DimacsErrorKind::VariableOutOfRange(n),
+            line,
+        ));
+    }
+
+    let named = declared.iter().map(|&(n, _, _)| n).max().unwrap_or(0);
+    let mut names: Vec<Option<Variable>> = vec![None; used.max(named)];
+    let mut taken = HashSet::new();
+
+    for (n, name, line) in declared {
+        if names[n - 1].is_some() || !taken.insert(name) {
+            return Err(DimacsError::new(DimacsErrorKind::DuplicateName(name), line));
+        }
+
+        names[n - 1] = Some(name);
+    }
+
+    let names = names
+        .into_iter()
+        .enumerate()
+        .map(|(i, name)| {
+            name.unwrap_or_else(|| {
+                let mut fallback = format!("x{}", i + 1);
+
+                while taken.contains(&Variable::new(&fallback)) {
+                    fallback.push('_');
+                }
+
+                Variable::new(&fallback)
+            })
+        })
+        .collect();
+
+    Ok((cnf, names))
+}
+
+// Variable i is called names[i], variables past those stay anonymous
+pub fn write(cnf: &Cnf, names: &[Variable]) -> String {
+    let mut lines: Vec<String> = names
+        .iter()
+        .enumerate()
+        .map(|(i, name)| format!("c var {} {name}", i + 1))
+        .collect();
+
+    lines.push(format!("p cnf {} {}", cnf.variables(), cnf.clauses().len()));
+
+    for clause in cnf.clauses() {
+        let literals = clause.iter().map(|literal| {
+            let n = literal.variable() as i64 + 1;
+
+            match literal.is_positive() {
+                true => n.to_string(),
+                false => (-n).to_string(),
+            }
+        });
+
+        lines.push(
+            literals
+                .chain(["0".to_string()])
+                .collect::<Vec<_>>()
+                .join(" "),
+        );
+    }
+
+    lines.join("\n") + "\n"
+}
+
+impl Node {
+    pub fn from_dimacs(input: &str) -> Result<Self, DimacsError> {
+        let (cnf, names) = read(input)?;
+
+        Ok(Node::from_clauses(&cnf, &names))
+    }
+
+    // The clauses of `to_clauses`, named after `variables()`
+    pub fn to_dimacs(&self) -> String {
+        write(&self.to_clauses(), &self.variables())
+    }
+}
+
+impl Display for DimacsError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "line {}: ", self.line)?;
+
+        match self.kind {
+            DimacsErrorKind::MissingHeader => write!(f, "missing 'p cnf' header"),
+            DimacsErrorKind::InvalidHeader => write!(f, "invalid 'p cnf' header"),
+            DimacsErrorKind::InvalidLiteral => write!(f, "invalid literal"),
+            DimacsErrorKind::VariableOutOfRange(v) => {
+                write!(f, "variable {v} exceeds the header's count")
+            }
+            DimacsErrorKind::DuplicateName(name) => write!(f, "variable name '{name}' taken"),
+            DimacsErrorKind::ClauseCount { expected, found } => {
+                write!(f, "expected {expected} clauses, found {found}")
+            }
+            DimacsErrorKind::UnterminatedClause => write!(f, "clause not terminated by 0"),
+        }
+    }
+}
+
+impl std::error::Error for DimacsError {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = "\
+c A small example
+c var 1 req_valid
+p cnf 3 4
+1 -2 0
+2 3
+0
+-1 0
+-3 0
+";
+
+    #[test]
+    fn read() {
+        let (cnf, names) = super::read(EXAMPLE).unwrap();
+
+        assert_eq!(cnf.variables(), 3);
+        assert_eq!(cnf.clauses().len(), 4);
+        assert_eq!(names, ["req_valid", "x2", "x3"].map(Variable::new).to_vec());
+        assert!(cnf.solve().is_none());
+    }
+
+    #[test]
+    fn large_header() {
+        // Names stop at the last variable used, however many the header declares
+        let (cnf, names) = super::read("p cnf 2000000000 1\n-3 0\n").unwrap();
+
+        assert_eq!(cnf.variables(), 2000000000);
+        assert_eq!(names, ["x1", "x2", "x3"].map(Variable::new).to_vec());
+    }
+
+    #[test]
+    fn node() {
+        let node = Node::from_dimacs(EXAMPLE).unwrap();
+
+        assert_eq!(
+            node.formula(),
+            "{req_valid}{x2}!|{x2}{x3}|{req_valid}!{x3}!&&&"
+        );
+        assert!(!node.sat());
+
+        // A declared x2 pushes the second variable's fallback name aside
+        let node = Node::from_dimacs("c var 1 x2\np cnf 2 2\n1 0\n-2 0\n").unwrap();
+
+        assert_eq!(node.formula(), "{x2}{x2_}!&");
+        assert!(node.sat());
+
+        let node = Node::from_dimacs("p cnf 1 0\n").unwrap();
+
+        assert!(node.evaluate_under(&Default::default()).unwrap());
+    }
+
+    #[test]
+    fn write() {
+        let node: Node = "AB|C!&".parse().unwrap();
+        let dimacs = node.to_dimacs();
+
+        assert_eq!(
+            dimacs,
+            "c var 1 A\nc var 2 B\nc var 3 C\np cnf 3 2\n1 2 0\n-3 0\n"
+        );
+        assert_eq!(Node::from_dimacs(&dimacs).unwrap().formula(), "AB|C!&");
+
+        let tseitin = node.tseitin();
+        let (cnf, _) = super::read(&super::write(tseitin.cnf(), tseitin.variables())).unwrap();
+
+        assert_eq!(&cnf, tseitin.cnf());
+    }
+
+    #[test]
+    fn errors() {
+        compare("1 2 0\n", DimacsErrorKind::MissingHeader, 1);
+        compare("p cnf x 1\n", DimacsErrorKind::InvalidHeader, 1);
+        compare("p dnf 1 1\n", DimacsErrorKind::InvalidHeader, 1);
+        compare("p cnf 4000000000 0\n", DimacsErrorKind::InvalidHeader, 1);
+        compare("p cnf 2 1\n1 a 0\n", DimacsErrorKind::InvalidLiteral, 2);
+        compare(
+            "p cnf 2 1\n1 -3 0\n",
+            DimacsErrorKind::VariableOutOfRange(3),
+            2,
+        );
+        compare("p cnf 2 1\n1 2\n", DimacsErrorKind::UnterminatedClause, 2);
+        compare(
+            "c var 999999999999 x\np cnf 2 0\n",
+            DimacsErrorKind::VariableOutOfRange(999999999999),
+            1,
+        );
+        compare(
+            "c var 1 a\nc var 2 a\np cnf 2 0\n",
+            DimacsErrorKind::DuplicateName(Variable::new("a")),
+            2,
+        );
+        compare(
+            "c var 1 a\nc var 1 b\np cnf 2 0\n",
+            DimacsErrorKind::DuplicateName(Variable::new("b")),
+            2,
+        );
+        compare(
+            "p cnf 2 2\n1 2 0\n",
+            DimacsErrorKind::ClauseCount {
+                expected: 2,
+                found: 1,
+            },
+            2,
+        );
+    }
+
+    fn compare(input: &str, kind: DimacsErrorKind, line: usize) {
+        let error = super::read(input).unwrap_err();
+
+        println!("{error}");
+
+        assert_eq!(error, DimacsError::new(kind, line));
+    }
+}
@@ -0,0 +1,73 @@
+use std::fmt::Display;
+
+use crate::{
+    dimacs::DimacsError,
+    node::{assignment::UnboundVariable, error::ParseError, variable::Variable},
+};
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Error {
+    Parse(ParseError),
+    Dimacs(DimacsError),
+    UnboundVariable(Variable),
+    MissingSet(Variable),
+    UnusedSet(Variable),
+    ExtraSets { expected: usize, found: usize },
+    OutsideUniverse(Variable),
+    ConstantInSet(bool),
+    TooManyVariables(usize),
+    TooManyElements(usize),
+    MintermOutOfRange(u64),
+}
+
+impl Display for Error {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Parse(error) => write!(f, "{error}"),
+            Self::Dimacs(error) => write!(f, "{error}"),
+            Self::UnboundVariable(v) => write!(f, "no value for variable '{v}'"),
+            Self::MissingSet(v) => write!(f, "no set for variable '{v}'"),
+            Self::UnusedSet(v) => write!(f, "set bound to '{v}', which is not in the formula"),
+            Self::ExtraSets { expected, found } => {
+                write!(f, "{found} sets for a formula of {expected} variables")
+            }
+            Self::OutsideUniverse(v) => {
+                write!(f, "set of variable '{v}' reaches outside the universe")
+            }
+            Self::ConstantInSet(b) => {
+                write!(f, "constant '{}' in set formula", *b as u8)
+            }
+            Self::TooManyVariables(n) => write!(f, "{n} variables, at most 64 fit a row or cube"),
+            Self::TooManyElements(n) => write!(f, "{n} elements, at most 32 fit a gray code"),
+            Self::MintermOutOfRange(row) => write!(f, "minterm {row} is not a row of the table"),
+        }
+    }
+}
+
+impl std::error::Error for Error {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::Parse(error) => Some(error),
+            Self::Dimacs(error) => Some(error),
+            _ => None,
+        }
+    }
+}
+
+impl From<ParseError> for Error {
+    fn from(error: ParseError) -> Self {
+        Self::Parse(error)
+    }
+}
+
+impl From<UnboundVariable> for Error {
+    fn from(error: UnboundVariable) -> Self {
+        Self::UnboundVariable(error.0)
+    }
+}
+
+impl From<DimacsError> for Error {
+    fn from(error: DimacsError) -> Self {
+        Self::Dimacs(error)
+    }
+}
@@ -0,0 +1,184 @@
+use error::Error;
+use node::{Node, assignment::Assignment};
+
+pub mod bdd;
+pub mod curve;
+pub mod dimacs;
+pub mod error;
+pub mod math;
+pub mod node;
+pub mod set;
+pub mod solver;
+
+pub fn eval_formula(formula: &str) -> bool {
+    try_eval_formula(formula).unwrap()
+}
+
+pub fn try_eval_formula(formula: &str) -> Result<bool, Error> {
+    formula.parse::<Node>()?.try_evaluate()
+}
+
+pub fn print_truth_table(formula: &str) {
+    try_print_truth_table(formula).unwrap()
+}
+
+pub fn try_print_truth_table(formula: &str) -> Result<(), Error> {
+    println!("{}", formula.parse::<Node>()?.truth_table());
+
+    Ok(())
+}
+
+pub fn negation_normal_form(formula: &str) -> String {
+    try_negation_normal_form(formula).unwrap()
+}
+
+pub fn try_negation_normal_form(formula: &str) -> Result<String, Error> {
+    let mut tree: Node = formula.parse()?;
+
+    tree.to_nnf();
+    Ok(tree.formula())
+}
+
+pub fn conjunctive_normal_form(formula: &str) -> String {
+    try_conjunctive_normal_form(formula).unwrap()
+}
+
+pub fn try_conjunctive_normal_form(formula: &str) -> Result<String, Error> {
+    let mut tree: Node = formula.parse()?;
+
+    tree.to_cnf();
+    tree.unbalance();
+    Ok(tree.formula())
+}
+
+pub fn disjunctive_normal_form(formula: &str) -> String {
+    try_disjunctive_normal_form(formula).unwrap()
+}
+
+pub fn try_disjunctive_normal_form(formula: &str) -> Result<String, Error> {
+    let mut tree: Node = formula.parse()?;
+
+    tree.to_dnf();
+    tree.unbalance();
+    Ok(tree.formula())
+}
+
+pub fn sat(formula: &str) -> bool {
+    try_sat(formula).unwrap()
+}
+
+pub fn try_sat(formula: &str) -> Result<bool, Error> {
+    Ok(formula.parse::<Node>()?.sat())
+}
+
+pub fn sat_model(formula: &str) -> Option<Assignment> {
+    try_sat_model(formula).unwrap()
+}
+
+pub fn try_sat_model(formula: &str) -> Result<Option<Assignment>, Error> {
+    Ok(formula.parse::<Node>()?.solve())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn eval() {
+        compare("10&", false);
+        compare("10|", true);
+        compare("11>", true);
+        compare("10=", false);
+        compare("1011||=", true);
+    }
+
+    #[test]
+    fn nnf() {
+        compare_nnf("AB&!", "A!B!|");
+        compare_nnf("AB|!", "A!B!&");
+        compare_nnf("AB>", "A!B|");
+        // compare_nnf("AB=", "AB&A!B!&|"); // Uses a different rewrite rule
+        compare_nnf("AB|C&!", "A!B!&C!|");
+    }
+
+    #[test]
+    fn cnf() {
+        compare_cnf("AB&!", "A!B!|");
+        compare_cnf("AB|!", "A!B!&");
+        compare_cnf("AB|C&", "AB|C&");
+        compare_cnf("AB|C|D|", "ABCD|||");
+        compare_cnf("AB&C&D&", "ABCD&&&");
+        compare_cnf("AB&!C!|", "A!B!C!||");
+        compare_cnf("AB|!C!&", "A!B!C!&&");
+    }
+
+    #[test]
+    fn dnf() {
+        compare_dnf("AB&!", "A!B!|");
+        compare_dnf("AB|!", "A!B!&");
+        compare_dnf("AB&C|", "AB&C|");
+        compare_dnf("AB|C&", "CA&CB&|");
+        compare_dnf("AB|C|D|", "ABCD|||");
+        compare_dnf("AB&C&D&", "ABCD&&&");
+        compare_dnf("AB|!C!&", "A!B!C!&&");
+    }
+
+    #[test]
+    fn sat() {
+        assert!(super::sat("AB|"));
+        assert!(super::sat("AB&"));
+        assert!(!super::sat("AA!&"));
+        assert!(!super::sat("AA^"));
+    }
+
+    #[test]
+    fn sat_model() {
+        let model = super::sat_model("AB>A&").unwrap();
+
+        assert_eq!(model.to_string(), "A=1 B=1");
+        assert_eq!(super::sat_model("AA!&"), None);
+    }
+
+    #[test]
+    fn errors() {
+        assert!(matches!(try_eval_formula("1&"), Err(Error::Parse(_))));
+        assert_eq!(
+            try_eval_formula("1A&"),
+            Err(Error::UnboundVariable(node::variable::Variable::from('A')))
+        );
+        assert!(try_negation_normal_form("AB").is_err());
+        assert!(try_conjunctive_normal_form("A?").is_err());
+        assert!(try_sat("").is_err());
+        assert!(try_print_truth_table("&").is_err());
+    }
+
+    fn compare(formula: &str, result: bool) {
+        println!("{formula}: {result}");
+
+        assert_eq!(eval_formula(formula), result);
+    }
+
+    fn compare_nnf(formula: &str, answer: &str) {
+        let nnf = negation_normal_form(formula);
+
+        println!("{formula}: {nnf} != {answer}");
+
+        assert_eq!(nnf, answer);
+    }
+
+    fn compare_dnf(formula: &str, answer: &str) {
+        let dnf = disjunctive_normal_form(formula);
+
+        println!("{formula}: {dnf} - {answer}");
+
+        assert_eq!(dnf, answer);
+    }
+
+    fn compare_cnf(formula: &str, answer: &str) {
+        let cnf = conjunctive_normal_form(formula);
+
+        println!("{formula}: {cnf} - {answer}");
+
+        assert_eq!(cnf, answer);
+    }
+}
@@ -1,99 +1,278 @@
-use node::Node;
+use std::{
+    env,
+    io::{self, BufRead},
+    process::ExitCode,
+};
 
-pub mod curve;
-pub mod math;
-pub mod node;
-pub mod set;
+use readysetboole::{error::Error, *};
 
-fn main() {}
+const USAGE: &str = "\
+usage: readysetboole <command> [arguments]
 
-pub fn eval_formula(formula: &str) -> bool {
-    formula.parse::<Node>().unwrap().evaluate()
+Formula commands read one formula per argument, or one per line from stdin:
+    eval [FORMULA...]         evaluate a formula without variables
+    table [FORMULA...]        print the truth table
+    nnf [FORMULA...]          print the negation normal form
+    cnf [FORMULA...]          print the conjunctive normal form
+    dnf [FORMULA...]          print the disjunctive normal form
+    dimacs [FORMULA...]       print the conjunctive normal form as DIMACS
+    sat [FORMULA...]          print a model, exit with 10 if satisfiable, 20 if not
+
+Other commands:
+    sets FORMULA SET...       evaluate a set formula, sets as 0,1,2 bound to the
+                              variables in alphabetical order
+    powerset [ELEMENT...]     print every subset
+    gray [N...]               print the gray code of every number
+    zmap X Y                  map a point onto the Z-order curve
+    zmap N                    map a curve value back onto a point
+
+Exit codes: 0 on success, 1 on invalid input, 2 on invalid usage.";
+
+const SATISFIABLE: u8 = 10;
+const UNSATISFIABLE: u8 = 20;
+
+enum Failure {
+    Usage(String),
+    Input,
 }
 
-pub fn print_truth_table(formula: &str) {
-    println!("{}", formula.parse::<Node>().unwrap().truth_table());
+fn main() -> ExitCode {
+    let args: Vec<String> = env::args().skip(1).collect();
+
+    let Some((command, args)) = args.split_first() else {
+        eprintln!("{USAGE}");
+
+        return ExitCode::from(2);
+    };
+
+    exit_code(run(command, args))
 }
 
-pub fn negation_normal_form(formula: &str) -> String {
-    let mut tree: Node = formula.parse().unwrap();
+fn exit_code(result: Result<ExitCode, Failure>) -> ExitCode {
+    match result {
+        Ok(code) => code,
+        Err(Failure::Usage(message)) => {
+            eprintln!("readysetboole: {message}\n\n{USAGE}");
 
-    tree.to_nnf();
-    tree.formula()
+            ExitCode::from(2)
+        }
+        Err(Failure::Input) => ExitCode::FAILURE,
+    }
 }
 
-pub fn conjunctive_normal_form(formula: &str) -> String {
-    let mut tree: Node = formula.parse().unwrap();
+fn run(command: &str, args: &[String]) -> Result<ExitCode, Failure> {
+    match command {
+        "eval" => each(args, |formula| {
+            println!("{}", try_eval_formula(formula)?);
 
-    tree.to_cnf();
-    tree.unbalance();
-    tree.formula()
+            Ok(())
+        }),
+        "table" => each(args, try_print_truth_table),
+        "nnf" => each(args, |formula| {
+            println!("{}", try_negation_normal_form(formula)?);
+
+            Ok(())
+        }),
+        "cnf" => each(args, |formula| {
+            println!("{}", try_conjunctive_normal_form(formula)?);
+
+            Ok(())
+        }),
+        "dnf" => each(args, |formula| {
+            println!("{}", try_disjunctive_normal_form(formula)?);
+
+            Ok(())
+        }),
+        "dimacs" => each(args, |formula| {
+            print!("{}", formula.parse::<node::Node>()?.to_dimacs());
+
+            Ok(())
+        }),
+        "sat" => sat(args),
+        "sets" => sets(args),
+        "powerset" => powerset(args),
+        "gray" => gray(args),
+        "zmap" => zmap(args),
+        "help" | "-h" | "--help" => {
+            println!("{USAGE}");
+
+            Ok(ExitCode::SUCCESS)
+        }
+        _ => Err(Failure::Usage(format!("unknown command '{command}'"))),
+    }
 }
 
-pub fn sat(formula: &str) -> bool {
-    formula.parse::<Node>().unwrap().sat()
+// Formulas from the arguments, or from stdin if there are none
+fn formulas(args: &[String]) -> Vec<String> {
+    if !args.is_empty() {
+        return args.to_vec();
+    }
+
+    io::stdin()
+        .lock()
+        .lines()
+        .map_while(Result::ok)
+        .map(|line| line.trim().to_string())
+        .filter(|line| !line.is_empty())
+        .collect()
 }
 
-#[cfg(test)]
-mod tests {
-    use super::*;
+fn each(args: &[String], f: impl Fn(&str) -> Result<(), Error>) -> Result<ExitCode, Failure> {
+    let mut failed = false;
 
-    #[test]
-    fn eval() {
-        compare("10&", false);
-        compare("10|", true);
-        compare("11>", true);
-        compare("10=", false);
-        compare("1011||=", true);
+    for formula in formulas(args) {
+        if let Err(error) = f(&formula) {
+            report(&formula, error);
+            failed = true;
+        }
     }
 
-    #[test]
-    fn nnf() {
-        compare_nnf("AB&!", "A!B!|");
-        compare_nnf("AB|!", "A!B!&");
-        compare_nnf("AB>", "A!B|");
-        // compare_nnf("AB=", "AB&A!B!&|"); // Uses a different rewrite rule
-        compare_nnf("AB|C&!", "A!B!&C!|");
+    match failed {
+        true => Err(Failure::Input),
+        false => Ok(ExitCode::SUCCESS),
     }
+}
 
-    #[test]
-    fn cnf() {
-        compare_cnf("AB&!", "A!B!|");
-        compare_cnf("AB|!", "A!B!&");
-        compare_cnf("AB|C&", "AB|C&");
-        compare_cnf("AB|C|D|", "ABCD|||");
-        compare_cnf("AB&C&D&", "ABCD&&&");
-        compare_cnf("AB&!C!|", "A!B!C!||");
-        compare_cnf("AB|!C!&", "A!B!C!&&");
+fn report(formula: &str, error: Error) {
+    eprintln!("readysetboole: {error}");
+
+    if let Error::Parse(error) = error {
+        eprintln!("{}", error.highlight(formula));
     }
+}
 
-    #[test]
-    fn sat() {
-        assert!(super::sat("AB|"));
-        assert!(super::sat("AB&"));
-        assert!(!super::sat("AA!&"));
-        assert!(!super::sat("AA^"));
+// Unsatisfiable as soon as one of the formulas is. Like `each`, it goes on past
+// invalid formulas, and then fails.
+fn sat(args: &[String]) -> Result<ExitCode, Failure> {
+    let mut code = SATISFIABLE;
+    let mut failed = false;
+
+    for formula in formulas(args) {
+        match try_sat_model(&formula) {
+            Ok(Some(model)) => println!("SAT {model}"),
+            Ok(None) => {
+                println!("UNSAT");
+                code = UNSATISFIABLE;
+            }
+            Err(error) => {
+                report(&formula, error);
+                failed = true;
+            }
+        }
     }
 
-    fn compare(formula: &str, result: bool) {
-        println!("{formula}: {result}");
+    match failed {
+        true => Err(Failure::Input),
+        false => Ok(ExitCode::from(code)),
+    }
+}
+
+fn sets(args: &[String]) -> Result<ExitCode, Failure> {
+    let Some((formula, sets)) = args.split_first() else {
+        return Err(Failure::Usage("missing set formula".to_string()));
+    };
+    let sets = sets
+        .iter()
+        .map(|set| numbers(set.split(',').filter(|s| !s.is_empty())))
+        .collect::<Result<Vec<Vec<i32>>, _>>()?;
 
-        assert_eq!(eval_formula(formula), result);
+    match set::try_eval_set(formula, sets) {
+        Ok(set) => {
+            println!(
+                "{}",
+                set.iter().map(i32::to_string).collect::<Vec<_>>().join(",")
+            );
+
+            Ok(ExitCode::SUCCESS)
+        }
+        Err(error) => {
+            report(formula, error);
+
+            Err(Failure::Input)
+        }
     }
+}
 
-    fn compare_nnf(formula: &str, answer: &str) {
-        let nnf = negation_normal_form(formula);
+fn powerset(args: &[String]) -> Result<ExitCode, Failure> {
+    for subset in set::powerset_iter(numbers::<i32>(args)?) {
+        println!("{subset:?}");
+    }
 
-        println!("{formula}: {nnf} != {answer}");
+    Ok(ExitCode::SUCCESS)
+}
 
-        assert_eq!(nnf, answer);
+fn gray(args: &[String]) -> Result<ExitCode, Failure> {
+    for n in numbers::<u32>(&formulas(args))? {
+        println!("{}", math::gray_code(n));
     }
 
-    fn compare_cnf(formula: &str, answer: &str) {
-        let cnf = conjunctive_normal_form(formula);
+    Ok(ExitCode::SUCCESS)
+}
+
+fn zmap(args: &[String]) -> Result<ExitCode, Failure> {
+    match args {
+        [x, y] => println!("{:?}", curve::map(number(x)?, number(y)?)),
+        [n] => {
+            let (x, y) = curve::reverse_map(number(n)?);
+
+            println!("{x} {y}");
+        }
+        _ => return Err(Failure::Usage("zmap takes one or two numbers".to_string())),
+    }
+
+    Ok(ExitCode::SUCCESS)
+}
+
+fn number<T: std::str::FromStr>(s: &str) -> Result<T, Failure> {
+    s.trim().parse().map_err(|_| {
+        eprintln!("readysetboole: invalid number '{s}'");
+
+        Failure::Input
+    })
+}
+
+fn numbers<T: std::str::FromStr>(
+    args: impl IntoIterator<Item = impl AsRef<str>>,
+) -> Result<Vec<T>, Failure> {
+    args.into_iter().map(|s| number(s.as_ref())).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn exit_codes() {
+        assert_eq!(code("eval", &["10|"]), ExitCode::SUCCESS);
+        assert_eq!(code("sat", &["AB&"]), ExitCode::from(10));
+        assert_eq!(code("sat", &["AB&", "AA!&"]), ExitCode::from(20));
+        assert_eq!(code("gray", &["1", "2"]), ExitCode::SUCCESS);
+        assert_eq!(code("sets", &["AB|", "0,1", "2"]), ExitCode::SUCCESS);
+        assert_eq!(code("zmap", &["3"]), ExitCode::SUCCESS);
+    }
+
+    #[test]
+    fn invalid_input() {
+        assert_eq!(code("eval", &["A&"]), ExitCode::FAILURE);
+        assert_eq!(code("eval", &["A"]), ExitCode::FAILURE);
+        assert_eq!(code("sat", &["A&", "AA!&"]), ExitCode::FAILURE);
+        assert_eq!(code("sat", &["AA!&", "A&"]), ExitCode::FAILURE);
+        assert_eq!(code("gray", &["abc"]), ExitCode::FAILURE);
+        assert_eq!(code("sets", &["AB|", "0,x", "2"]), ExitCode::FAILURE);
+        assert_eq!(code("zmap", &["1", "-2"]), ExitCode::FAILURE);
+        assert_eq!(code("powerset", &["1", "a"]), ExitCode::FAILURE);
+    }
+
+    #[test]
+    fn invalid_usage() {
+        assert_eq!(code("frobnicate", &[]), ExitCode::from(2));
+        assert_eq!(code("sets", &[]), ExitCode::from(2));
+        assert_eq!(code("zmap", &["1", "2", "3"]), ExitCode::from(2));
+    }
 
-        println!("{formula}: {cnf} - {answer}");
+    fn code(command: &str, args: &[&str]) -> ExitCode {
+        let args: Vec<String> = args.iter().map(|arg| arg.to_string()).collect();
 
-        assert_eq!(cnf, answer);
+        exit_code(run(command, &args))
     }
 }
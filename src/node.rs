@@ -1,12 +1,27 @@
+pub mod assignment;
+pub mod bdd;
 pub mod clause;
+pub mod cnf;
+pub mod equivalence;
+pub mod error;
+pub mod espresso;
+pub mod infix;
+pub mod minimize;
 pub mod rewrite;
 pub mod string;
 pub mod table;
-
+pub mod tseitin;
+pub mod variable;
+
+use crate::{
+    error::Error,
+    set::bitset::{Bitset, Universe},
+};
+use assignment::Assignment;
 use clause::*;
-use itertools::Itertools;
+use variable::Variable;
 
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, PartialEq, Eq)]
 pub struct Node {
     clause: Clause,
     left: Option<Box<Node>>,
@@ -42,14 +57,14 @@ impl Node {
         self.left
             .as_deref()
             .into_iter()
-            .chain(self.right.as_deref().into_iter())
+            .chain(self.right.as_deref())
     }
 
     fn children_mut(&mut self) -> impl Iterator<Item = &mut Node> {
         self.left
             .as_deref_mut()
             .into_iter()
-            .chain(self.right.as_deref_mut().into_iter())
+            .chain(self.right.as_deref_mut())
     }
 
     fn foreach_mut(&mut self, f: fn(&mut Self)) {
@@ -67,6 +82,28 @@ impl Node {
             .unwrap_or(0)
     }
 
+    fn literal(variable: Variable, positive: bool) -> Box<Node> {
+        let variable = Box::new(Node::new(Clause::Variable(variable), None, None));
+
+        match positive {
+            true => variable,
+            false => Box::new(Node::new(Clause::Negation, Some(variable), None)),
+        }
+    }
+
+    // Operands joined by `operator` and nested to the right, as after `unbalance`
+    fn chain(
+        operator: Clause,
+        identity: bool,
+        operands: impl IntoIterator<Item = Box<Node>, IntoIter: DoubleEndedIterator>,
+    ) -> Box<Node> {
+        operands
+            .into_iter()
+            .rev()
+            .reduce(|right, left| Box::new(Node::new(operator, Some(left), Some(right))))
+            .unwrap_or_else(|| Box::new(Node::new(Clause::Value(identity), None, None)))
+    }
+
     pub fn formula(&self) -> String {
         let mut formula = String::new();
 
@@ -74,7 +111,7 @@ impl Node {
             formula.push_str(&child.formula());
         }
 
-        formula.push(self.clause.to());
+        formula.push_str(&self.clause.to());
 
         formula
     }
@@ -84,14 +121,18 @@ impl Node {
         self.evaluate_with(|_| panic!("Unsolved variables"))
     }
 
-    fn evaluate_with(&self, f: impl Fn(char) -> bool + Copy) -> bool {
+    pub fn try_evaluate(&self) -> Result<bool, Error> {
+        Ok(self.evaluate_under(&Assignment::new())?)
+    }
+
+    fn evaluate_with(&self, f: impl Fn(Variable) -> bool + Copy) -> bool {
         let left = || self.left().evaluate_with(f);
         let right = || self.right().evaluate_with(f);
 
         match self.clause {
-            Clause::Variable(v) => return f(v),
-            Clause::Value(b) => return b,
-            Clause::Negation => return !left(),
+            Clause::Variable(v) => f(v),
+            Clause::Value(b) => b,
+            Clause::Negation => !left(),
             Clause::Conjunction => left() & right(),
             Clause::Disjunction => left() | right(),
             Clause::Exclusive => left() ^ right(),
@@ -100,55 +141,46 @@ impl Node {
         }
     }
 
-    pub fn evaluate_sets(
+    // Sorted, without duplicates. Complements are taken within the universe.
+    pub fn evaluate_sets<T: Ord + Clone>(
         &self,
-        encompassing: &Vec<i32>,
-        f: impl Copy + Fn(char) -> Vec<i32>,
-    ) -> Vec<i32> {
-        match self.clause {
-            Clause::Variable(v) => return f(v),
-            Clause::Value(_) => panic!(),
-            _ => (),
-        }
-
-        let left = self.left().evaluate_sets(encompassing, f);
+        universe: &Universe<T>,
+        f: impl Copy + Fn(Variable) -> Option<Vec<T>>,
+    ) -> Result<Vec<T>, Error> {
+        let bits = self.evaluate_bits(|v| {
+            let set = f(v).ok_or(Error::MissingSet(v))?;
 
-        match self.clause {
-            Clause::Negation => {
-                return encompassing
-                    .clone()
-                    .into_iter()
-                    .filter(|x| !left.contains(x))
-                    .collect();
-            }
-            _ => (),
-        }
+            universe.bitset(&set).ok_or(Error::OutsideUniverse(v))
+        })?;
 
-        let right = self.right().evaluate_sets(encompassing, f);
-        let clone = right.clone();
+        Ok(universe.set(&bits))
+    }
 
+    // The leftmost constant, which set formulas may not hold
+    pub(crate) fn constant(&self) -> Option<bool> {
         match self.clause {
-            Clause::Conjunction => left.into_iter().filter(|x| right.contains(x)).collect(),
-            Clause::Disjunction => left.into_iter().chain(right.into_iter()).unique().collect(),
-            Clause::Exclusive => left
-                .clone()
-                .into_iter()
-                .filter(move |x| !clone.clone().contains(x))
-                .chain(right.into_iter().filter(|x| !left.contains(x)))
-                .collect(),
-            Clause::Material => encompassing
-                .clone()
-                .into_iter()
-                .filter(|x| right.contains(x) || !left.contains(x))
-                .unique()
-                .collect(),
-            Clause::Equivalence => encompassing
-                .clone()
-                .into_iter()
-                .filter(|x| left.contains(x) == right.contains(x))
-                .unique()
-                .collect(),
-            _ => unreachable!(),
+            Clause::Value(b) => Some(b),
+            _ => self.children().find_map(Node::constant),
         }
     }
+
+    // Every operator a word of 64 elements at a time
+    pub fn evaluate_bits(
+        &self,
+        f: impl Copy + Fn(Variable) -> Result<Bitset, Error>,
+    ) -> Result<Bitset, Error> {
+        let left = || self.left().evaluate_bits(f);
+        let right = || self.right().evaluate_bits(f);
+
+        Ok(match self.clause {
+            Clause::Variable(v) => f(v)?,
+            Clause::Value(b) => return Err(Error::ConstantInSet(b)),
+            Clause::Negation => !&left()?,
+            Clause::Conjunction => &left()? & &right()?,
+            Clause::Disjunction => &left()? | &right()?,
+            Clause::Exclusive => &left()? ^ &right()?,
+            Clause::Material => &!&left()? | &right()?,
+            Clause::Equivalence => !&(&left()? ^ &right()?),
+        })
+    }
 }
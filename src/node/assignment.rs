@@ -0,0 +1,133 @@
+use std::{collections::BTreeMap, fmt::Display};
+
+use super::{Node, variable::Variable};
+
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Assignment(BTreeMap<Variable, bool>);
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct UnboundVariable(pub Variable);
+
+impl Assignment {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    // The first variable is the most significant bit, like a truth table row;
+    // variables before the last 64 are false
+    pub fn from_bits(variables: &[Variable], bits: u64) -> Self {
+        variables
+            .iter()
+            .rev()
+            .enumerate()
+            .map(|(i, &v)| (v, bits.checked_shr(i as u32).unwrap_or(0) & 1 == 1))
+            .collect()
+    }
+
+    pub fn set(&mut self, variable: Variable, value: bool) {
+        self.0.insert(variable, value);
+    }
+
+    pub fn get(&self, variable: Variable) -> Option<bool> {
+        self.0.get(&variable).copied()
+    }
+
+    pub fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    // Sorted by variable name
+    pub fn iter(&self) -> impl Iterator<Item = (Variable, bool)> {
+        self.0.iter().map(|(&v, &b)| (v, b))
+    }
+}
+
+impl Node {
+    pub fn evaluate_under(&self, assignment: &Assignment) -> Result<bool, UnboundVariable> {
+        if let Some(v) = self
+            .variables()
+            .into_iter()
+            .find(|&v| assignment.get(v).is_none())
+        {
+            return Err(UnboundVariable(v));
+        }
+
+        Ok(self.evaluate_with(|v| assignment.0[&v]))
+    }
+}
+
+impl FromIterator<(Variable, bool)> for Assignment {
+    fn from_iter<T: IntoIterator<Item = (Variable, bool)>>(iter: T) -> Self {
+        Self(iter.into_iter().collect())
+    }
+}
+
+impl Display for Assignment {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let pairs: Vec<String> = self
+            .iter()
+            .map(|(v, b)| format!("{v}={}", b as u8))
+            .collect();
+
+        write!(f, "{}", pairs.join(" "))
+    }
+}
+
+impl Display for UnboundVariable {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "no value for variable '{}'", self.0)
+    }
+}
+
+impl std::error::Error for UnboundVariable {}
+
+#[cfg(test)]
+mod tests {
+    use crate::node::{assignment::*, *};
+
+    #[test]
+    fn bits() {
+        let variables = ['A', 'B', 'C'].map(Variable::from);
+        let assignment = Assignment::from_bits(&variables, 0b110);
+
+        assert_eq!(assignment.get(variables[0]), Some(true));
+        assert_eq!(assignment.get(variables[1]), Some(true));
+        assert_eq!(assignment.get(variables[2]), Some(false));
+        assert_eq!(assignment.get(Variable::from('D')), None);
+        assert_eq!(assignment.to_string(), "A=1 B=1 C=0");
+
+        let variables: Vec<_> = (0..70).map(|i| Variable::new(&format!("x{i}"))).collect();
+        let assignment = Assignment::from_bits(&variables, 1 << 63 | 1);
+
+        assert_eq!(assignment.iter().filter(|&(_, b)| b).count(), 2);
+        assert_eq!(assignment.get(variables[5]), Some(false));
+        assert_eq!(assignment.get(variables[6]), Some(true));
+        assert_eq!(assignment.get(variables[69]), Some(true));
+    }
+
+    #[test]
+    fn evaluate() {
+        let node = Node::from_infix("req_valid & !stall | A").unwrap();
+        let mut assignment: Assignment = [
+            (Variable::new("req_valid"), true),
+            (Variable::new("stall"), false),
+        ]
+        .into_iter()
+        .collect();
+
+        assert_eq!(
+            node.evaluate_under(&assignment),
+            Err(UnboundVariable(Variable::from('A')))
+        );
+
+        assignment.set(Variable::from('A'), false);
+        assert_eq!(node.evaluate_under(&assignment), Ok(true));
+
+        assignment.set(Variable::new("stall"), true);
+        assert_eq!(node.evaluate_under(&assignment), Ok(false));
+    }
+}
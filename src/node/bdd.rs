@@ -0,0 +1,116 @@
+use itertools::Itertools;
+
+use super::{Clause, Node, variable::Variable};
+use crate::bdd::{Bdd, Manager};
+
+// Static variable orders, read off the formula before building its diagram
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Heuristic {
+    // As `variables`
+    Alphabetical,
+    // First appearance from left to right, keeping the variables of a subformula together
+    DepthFirst,
+    // Each operator splits its weight among its operands and variables are sorted by
+    // the weight they collect, so those deciding most of the formula come first
+    Weight,
+}
+
+impl Node {
+    pub fn variable_order(&self, heuristic: Heuristic) -> Vec<Variable> {
+        match heuristic {
+            Heuristic::Alphabetical => self.variables(),
+            Heuristic::DepthFirst => self.occurrences().into_iter().unique().collect(),
+            Heuristic::Weight => {
+                let mut weights: Vec<(Variable, f64)> = Vec::new();
+
+                self.weigh(1.0, &mut weights);
+
+                weights
+                    .into_iter()
+                    .into_grouping_map()
+                    .sum()
+                    .into_iter()
+                    .sorted_by(|a, b| b.1.total_cmp(&a.1).then(a.0.cmp(&b.0)))
+                    .map(|(v, _)| v)
+                    .collect()
+            }
+        }
+    }
+
+    fn weigh(&self, weight: f64, weights: &mut Vec<(Variable, f64)>) {
+        match self.clause {
+            Clause::Variable(v) => weights.push((v, weight)),
+            _ => {
+                let share = weight / self.children().count() as f64;
+
+                for child in self.children() {
+                    child.weigh(share, weights);
+                }
+            }
+        }
+    }
+
+    // The formula's variables that are new to the manager go below the others, sorted.
+    // It is built as one operation, reordered automatically only once done.
+    pub fn to_bdd(&self, manager: &mut Manager) -> Bdd {
+        for v in self.variables() {
+            manager.declare(v);
+        }
+
+        manager.operation(|manager| self.build(manager))
+    }
+
+    // A Shannon expansion at every node, shared subdiagrams written out each time
+    pub fn from_bdd(manager: &Manager, f: Bdd) -> Self {
+        let Some((v, low, high)) = manager.node(f) else {
+            return Node::new(Clause::Value(f == Bdd::TRUE), None, None);
+        };
+        let join = |operator, left, right: Bdd| {
+            Node::new(
+                operator,
+                Some(left),
+                Some(Box::new(Node::from_bdd(manager, right))),
+            )
+        };
+
+        match (low, high) {
+            (Bdd::FALSE, Bdd::TRUE) => *Node::literal(v, true),
+            (Bdd::TRUE, Bdd::FALSE) => *Node::literal(v, false),
+            (Bdd::FALSE, high) => join(Clause::Conjunction, Node::literal(v, true), high),
+            (Bdd::TRUE, high) => join(Clause::Disjunction, Node::literal(v, false), high),
+            (low, Bdd::FALSE) => join(Clause::Conjunction, Node::literal(v, false), low),
+            (low, Bdd::TRUE) => join(Clause::Disjunction, Node::literal(v, true), low),
+            (low, high) => Node::new(
+                Clause::Disjunction,
+                Some(Box::new(join(
+                    Clause::Conjunction,
+                    Node::literal(v, true),
+                    high,
+                ))),
+                Some(Box::new(join(
+                    Clause::Conjunction,
+                    Node::literal(v, false),
+                    low,
+                ))),
+            ),
+        }
+    }
+
+    fn build(&self, manager: &mut Manager) -> Bdd {
+        match self.clause {
+            Clause::Value(b) => Bdd::constant(b),
+            Clause::Variable(v) => manager.variable(v),
+            Clause::Negation => {
+                let f = self.left().build(manager);
+
+                manager.not(f)
+            }
+            operator => {
+                let f = self.left().build(manager);
+                let g = self.right().build(manager);
+
+                manager.apply(operator, f, g)
+            }
+        }
+    }
+}
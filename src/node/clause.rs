@@ -1,8 +1,10 @@
-#[derive(Clone, Copy, Debug, PartialEq, PartialOrd)]
+use super::variable::Variable;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd)]
 pub enum Clause {
     // Operands
     Value(bool),
-    Variable(char),
+    Variable(Variable),
 
     // Operators
     Negation,
@@ -14,53 +16,90 @@ pub enum Clause {
 }
 
 impl Clause {
-    pub fn from(c: char) -> Self {
-        match c {
+    pub fn from(c: char) -> Option<Self> {
+        let clause = match c {
             '0' => Self::Value(false),
             '1' => Self::Value(true),
-            'A'..='Z' => Self::Variable(c),
+            'A'..='Z' => Self::Variable(Variable::from(c)),
             '!' => Self::Negation,
             '&' => Self::Conjunction,
             '|' => Self::Disjunction,
             '^' => Self::Exclusive,
             '>' => Self::Material,
             '=' => Self::Equivalence,
-            _ => panic!("Invalid clause"),
-        }
+            _ => return None,
+        };
+
+        Some(clause)
     }
 
-    pub fn to(self) -> char {
-        match self {
+    pub fn to(self) -> String {
+        let c = match self {
             Self::Value(false) => '0',
             Self::Value(true) => '1',
-            Self::Variable(v) => v,
+            Self::Variable(v) => return v.rpn(),
             Self::Negation => '!',
             Self::Conjunction => '&',
             Self::Disjunction => '|',
             Self::Exclusive => '^',
             Self::Material => '>',
             Self::Equivalence => '=',
-        }
+        };
+
+        c.to_string()
     }
 
-    pub fn symbol(self) -> char {
-        match self {
+    pub fn from_symbol(c: char) -> Option<Self> {
+        let clause = match c {
+            '⊥' => Self::Value(false),
+            '⊤' => Self::Value(true),
+            '¬' => Self::Negation,
+            '∧' => Self::Conjunction,
+            '∨' => Self::Disjunction,
+            '⊕' => Self::Exclusive,
+            '⇒' => Self::Material,
+            '⇔' => Self::Equivalence,
+            _ => return None,
+        };
+
+        Some(clause)
+    }
+
+    pub fn symbol(self) -> String {
+        let c = match self {
             Self::Value(false) => '⊥',
             Self::Value(true) => '⊤',
+            Self::Variable(v) => return v.name().to_string(),
             Self::Negation => '¬',
             Self::Conjunction => '∧',
             Self::Disjunction => '∨',
             Self::Exclusive => '⊕',
             Self::Material => '⇒',
             Self::Equivalence => '⇔',
-            _ => self.to(),
-        }
+        };
+
+        c.to_string()
     }
 
     pub fn is_operand(self) -> bool {
+        matches!(self, Self::Value(_) | Self::Variable(_))
+    }
+
+    // Binding strength in infix notation, higher binds tighter
+    pub fn precedence(self) -> u8 {
         match self {
-            Self::Value(_) | Self::Variable(_) => true,
-            _ => false,
+            Self::Value(_) | Self::Variable(_) => 6,
+            Self::Negation => 5,
+            Self::Conjunction => 4,
+            Self::Exclusive => 3,
+            Self::Disjunction => 2,
+            Self::Material => 1,
+            Self::Equivalence => 0,
         }
     }
+
+    // A ⇒ B ⇒ C reads as A ⇒ (B ⇒ C), every other operator groups to the left
+    pub fn is_right_associative(self) -> bool {
+        self == Self::Material
+    }
 }
@@ -0,0 +1,145 @@
+use super::{Clause, Node, assignment::Assignment, variable::Variable};
+use crate::solver::{Cnf, Literal};
+
+impl Node {
+    // Variable i of the clause database is the i-th of `variables()`. A formula
+    // already in conjunctive normal form gives its own clauses, anything else the
+    // equisatisfiable Tseitin encoding, whose later variables name subformulas.
+    pub fn to_clauses(&self) -> Cnf {
+        self.clauses()
+            .unwrap_or_else(|| self.tseitin().cnf().clone())
+    }
+
+    // None unless the formula is a conjunction of disjunctions of literals
+    fn clauses(&self) -> Option<Cnf> {
+        let variables = self.variables();
+        let mut cnf = Cnf::new(variables.len());
+
+        'clauses: for conjunct in self.flatten(Clause::Conjunction) {
+            let mut clause = Vec::new();
+
+            for disjunct in conjunct.flatten(Clause::Disjunction) {
+                let (node, positive) = match disjunct.clause {
+                    Clause::Negation => (disjunct.left(), false),
+                    _ => (disjunct, true),
+                };
+
+                match node.clause {
+                    Clause::Variable(v) => {
+                        clause.push(Literal::new(variables.binary_search(&v).unwrap(), positive))
+                    }
+                    Clause::Value(b) if b == positive => continue 'clauses,
+                    Clause::Value(_) => (),
+                    _ => return None,
+                }
+            }
+
+            cnf.add_clause(clause);
+        }
+
+        Some(cnf)
+    }
+
+    // Conjunctions and disjunctions nest to the right, as after `unbalance`
+    pub fn from_clauses(cnf: &Cnf, names: &[Variable]) -> Self {
+        let clauses = cnf.clauses().iter().map(|clause| {
+            let literals = clause
+                .iter()
+                .map(|literal| Node::literal(names[literal.variable()], literal.is_positive()));
+
+            Node::chain(Clause::Disjunction, false, literals)
+        });
+
+        *Node::chain(Clause::Conjunction, true, clauses)
+    }
+
+    pub fn sat(&self) -> bool {
+        self.solve().is_some()
+    }
+
+    // A satisfying assignment of every variable, if there is one
+    pub fn solve(&self) -> Option<Assignment> {
+        self.tseitin().solve()
+    }
+
+    // The operands of a chain of the same operator
+    pub(super) fn flatten(&self, operator: Clause) -> Vec<&Node> {
+        match self.clause == operator {
+            true => self
+                .children()
+                .flat_map(|node| node.flatten(operator))
+                .collect(),
+            false => vec![self],
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::node::{assignment::Assignment, *};
+
+    #[test]
+    fn solve() {
+        let node: Node = "AB!&C|C!&".parse().unwrap();
+        let model = node.solve().unwrap();
+
+        assert_eq!(model.to_string(), "A=1 B=0 C=0");
+        assert_eq!(node.evaluate_under(&model), Ok(true));
+        assert!(node.sat());
+
+        let node: Node = "AB&A!&".parse().unwrap();
+
+        assert_eq!(node.solve(), None);
+        assert!(!node.sat());
+    }
+
+    #[test]
+    fn clauses() {
+        let node: Node = "AB!|C&".parse().unwrap();
+
+        assert_eq!(node.to_clauses().clauses().len(), 2);
+        assert_eq!(node.to_clauses().variables(), 3);
+
+        // Linear where distributing would need 2^20 clauses
+        let names: Vec<String> = (0..20).map(|i| format!("(a{i} & b{i})")).collect();
+        let node = Node::from_infix(&names.join(" | ")).unwrap();
+        let cnf = node.to_clauses();
+
+        assert!(cnf.clauses().len() <= 3 * names.len() + 1);
+        assert!(cnf.solve().is_some());
+    }
+
+    #[test]
+    fn constants() {
+        for (formula, sat) in [
+            ("1", true),
+            ("0", false),
+            ("A0&", false),
+            ("A1|", true),
+            ("A!0|", true),
+            ("1!A&", false),
+        ] {
+            let node: Node = formula.parse().unwrap();
+
+            assert_eq!(node.sat(), sat);
+            assert_eq!(node.sat(), brute(&node));
+        }
+    }
+
+    #[test]
+    fn names() {
+        let node = Node::from_infix("(x_1 -> x_2) & (x_2 -> x_3) & x_1 & !x_3").unwrap();
+
+        assert!(!node.sat());
+        assert!(!brute(&node));
+    }
+
+    fn brute(node: &Node) -> bool {
+        let variables = node.variables();
+
+        (0..1 << variables.len()).any(|i| {
+            node.evaluate_under(&Assignment::from_bits(&variables, i))
+                .unwrap()
+        })
+    }
+}
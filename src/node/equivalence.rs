@@ -0,0 +1,140 @@
+use super::{Clause, Node, assignment::Assignment};
+
+impl Node {
+    // An assignment under which the two differ, if there is one: a model of ¬(A ⇔ B)
+    pub fn equivalent(&self, other: &Node) -> Result<(), Assignment> {
+        match self.join(Clause::Equivalence, other).negated().solve() {
+            Some(assignment) => Err(assignment),
+            None => Ok(()),
+        }
+    }
+
+    // An assignment under which this holds and `other` does not: a model of A ∧ ¬B
+    pub fn entails(&self, other: &Node) -> Result<(), Assignment> {
+        match self.join(Clause::Conjunction, &other.negated()).solve() {
+            Some(assignment) => Err(assignment),
+            None => Ok(()),
+        }
+    }
+
+    // An assignment under which every one of `premises` holds and this does not
+    pub fn follows_from(&self, premises: &[Node]) -> Result<(), Assignment> {
+        Node::chain(
+            Clause::Conjunction,
+            true,
+            premises.iter().cloned().map(Box::new),
+        )
+        .entails(self)
+    }
+
+    pub fn is_tautology(&self) -> bool {
+        self.falsify().is_none()
+    }
+
+    pub fn is_contradiction(&self) -> bool {
+        !self.sat()
+    }
+
+    // An assignment under which the formula does not hold
+    pub fn falsify(&self) -> Option<Assignment> {
+        self.negated().solve()
+    }
+
+    fn negated(&self) -> Node {
+        Node::new(Clause::Negation, Some(Box::new(self.clone())), None)
+    }
+
+    pub(crate) fn join(&self, operator: Clause, other: &Node) -> Node {
+        Node::new(
+            operator,
+            Some(Box::new(self.clone())),
+            Some(Box::new(other.clone())),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::node::*;
+
+    #[test]
+    fn equivalent() {
+        compare("AB&!", "A!B!|", None);
+        compare("AB>", "B!A!>", None);
+        compare("AB^", "AB=!", None);
+        compare("AB>", "BA>", Some("A=0 B=1"));
+        compare("AB&C|", "AB|C&", Some("A=0 B=0 C=1"));
+
+        // Variables only one side has are still assigned
+        let a: Node = "AB|".parse().unwrap();
+        let b: Node = "AC|".parse().unwrap();
+        let assignment = a.equivalent(&b).unwrap_err();
+
+        assert_eq!(assignment.len(), 3);
+        assert_ne!(a.evaluate_under(&assignment), b.evaluate_under(&assignment));
+    }
+
+    #[test]
+    fn follows_from() {
+        let premises: Vec<Node> = ["AB>", "BC>"].map(|f| f.parse().unwrap()).into();
+        let conclusion: Node = "AC>".parse().unwrap();
+
+        assert_eq!(conclusion.follows_from(&premises), Ok(()));
+        assert_eq!(
+            conclusion
+                .follows_from(&premises[..1])
+                .unwrap_err()
+                .to_string(),
+            "A=1 B=1 C=0"
+        );
+        assert!(conclusion.follows_from(&[]).is_err());
+    }
+
+    #[test]
+    fn entails() {
+        let a: Node = "AB&".parse().unwrap();
+        let b: Node = "AB|".parse().unwrap();
+
+        assert_eq!(a.entails(&b), Ok(()));
+
+        let assignment = b.entails(&a).unwrap_err();
+
+        assert_eq!(b.evaluate_under(&assignment), Ok(true));
+        assert_eq!(a.evaluate_under(&assignment), Ok(false));
+    }
+
+    #[test]
+    fn tautology() {
+        for (formula, tautology, contradiction) in [
+            ("AA!|", true, false),
+            ("AA!&", false, true),
+            ("AB>A&B>", true, false),
+            ("AB|", false, false),
+            ("1", true, false),
+            ("0", false, true),
+        ] {
+            let node: Node = formula.parse().unwrap();
+
+            assert_eq!(node.is_tautology(), tautology);
+            assert_eq!(node.is_contradiction(), contradiction);
+
+            match node.falsify() {
+                Some(assignment) => assert_eq!(node.evaluate_under(&assignment), Ok(false)),
+                None => assert!(tautology),
+            }
+        }
+    }
+
+    fn compare(a: &str, b: &str, expected: Option<&str>) {
+        let a: Node = a.parse().unwrap();
+        let b: Node = b.parse().unwrap();
+        let result = a.equivalent(&b);
+
+        println!("{a} {b} {result:?}");
+
+        match expected {
+            Some(assignment) => assert_eq!(result.unwrap_err().to_string(), assignment),
+            None => assert_eq!(result, Ok(())),
+        }
+    }
+}
@@ -0,0 +1,74 @@
+use std::fmt::Display;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ParseErrorKind {
+    EmptyInput,
+    UnknownSymbol,
+    MissingOperand,
+    TrailingOperands,
+    UnexpectedSymbol,
+    UnbalancedParenthesis,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct ParseError {
+    kind: ParseErrorKind,
+    position: usize,
+    symbol: Option<char>,
+}
+
+impl ParseError {
+    pub fn new(kind: ParseErrorKind, position: usize, symbol: Option<char>) -> Self {
+        Self {
+            kind,
+            position,
+            symbol,
+        }
+    }
+
+    pub fn kind(&self) -> ParseErrorKind {
+        self.kind
+    }
+
+    // Offset in characters, not bytes
+    pub fn position(&self) -> usize {
+        self.position
+    }
+
+    pub fn symbol(&self) -> Option<char> {
+        self.symbol
+    }
+
+    // The formula with a caret under the offending symbol
+    pub fn highlight(&self, formula: &str) -> String {
+        format!("{formula}\n{}^", " ".repeat(self.position))
+    }
+}
+
+impl Display for ParseError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let symbol = self.symbol.unwrap_or(' ');
+        let position = self.position;
+
+        match self.kind {
+            ParseErrorKind::EmptyInput => write!(f, "empty formula"),
+            ParseErrorKind::UnknownSymbol => {
+                write!(f, "unknown symbol '{symbol}' at position {position}")
+            }
+            ParseErrorKind::MissingOperand => {
+                write!(f, "missing operand for '{symbol}' at position {position}")
+            }
+            ParseErrorKind::TrailingOperands => {
+                write!(f, "trailing operand '{symbol}' at position {position}")
+            }
+            ParseErrorKind::UnexpectedSymbol => {
+                write!(f, "unexpected symbol '{symbol}' at position {position}")
+            }
+            ParseErrorKind::UnbalancedParenthesis => {
+                write!(f, "unbalanced '{symbol}' at position {position}")
+            }
+        }
+    }
+}
+
+impl std::error::Error for ParseError {}
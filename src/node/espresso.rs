@@ -0,0 +1,451 @@
+use super::{
+    Clause, Node,
+    minimize::{Cube, Form},
+    variable::Variable,
+};
+use crate::error::Error;
+
+// Heuristic two-level minimization in the manner of Espresso. Starting from any cover
+// of the on-set, every cube is grown as far as the on- and don't-care-sets allow, the
+// cubes the others already cover are dropped, and the rest shrunk again to grow in
+// another direction, for as long as the cover gets cheaper. Containment is decided on
+// the cubes themselves, so the truth table is never built. Cubes fit 64 variables.
+pub fn espresso(variables: usize, on: &[Cube], dont_care: &[Cube]) -> Result<Vec<Cube>, Error> {
+    if variables > 64 {
+        return Err(Error::TooManyVariables(variables));
+    }
+
+    let mut cover = on.to_vec();
+
+    expand(&mut cover, dont_care);
+    irredundant(&mut cover, dont_care);
+
+    let mut best = cover.clone();
+
+    loop {
+        reduce(&mut cover, dont_care, variables);
+        expand(&mut cover, dont_care);
+        irredundant(&mut cover, dont_care);
+
+        match cost(&cover) < cost(&best) {
+            true => best = cover.clone(),
+            false => return Ok(best),
+        }
+    }
+}
+
+// Fewest cubes, then fewest literals
+fn cost(cover: &[Cube]) -> (usize, u32) {
+    (cover.len(), cover.iter().map(Cube::literals).sum())
+}
+
+// Drop every literal whose removal keeps the cube inside the cover, largest cubes first,
+// then the cubes swallowed by the grown ones
+fn expand(cover: &mut Vec<Cube>, dont_care: &[Cube]) {
+    cover.sort_by_key(Cube::literals);
+
+    for i in 0..cover.len() {
+        let mut cube = cover[i];
+
+        if cover[..i].iter().any(|other| contains(*other, cube)) {
+            continue;
+        }
+
+        for bit in (0..64).map(|b| 1 << b) {
+            if cube.care() & bit == 0 {
+                continue;
+            }
+
+            let grown = Cube::new(cube.value(), cube.care() & !bit);
+
+            if covers(cover.iter().chain(dont_care), grown) {
+                cube = grown;
+            }
+        }
+
+        cover[i] = cube;
+    }
+
+    let mut kept: Vec<Cube> = Vec::new();
+
+    for &cube in cover.iter() {
+        if !kept.iter().any(|other| contains(*other, cube)) {
+            kept.push(cube);
+        }
+    }
+
+    *cover = kept;
+}
+
+// Remove cubes covered by the others, the smallest first
+fn irredundant(cover: &mut Vec<Cube>, dont_care: &[Cube]) {
+    cover.sort_by_key(|cube| std::cmp::Reverse(cube.literals()));
+
+    let mut i = 0;
+
+    while i < cover.len() {
+        let cube = cover.remove(i);
+
+        if !covers(cover.iter().chain(dont_care), cube) {
+            cover.insert(i, cube);
+            i += 1;
+        }
+    }
+}
+
+// Shrink every cube to the part the others do not cover, one half at a time
+fn reduce(cover: &mut Vec<Cube>, dont_care: &[Cube], variables: usize) {
+    cover.sort_by_key(Cube::literals);
+
+    let mut i = 0;
+
+    while i < cover.len() {
+        let mut cube = cover.remove(i);
+        let others = || cover.iter().chain(dont_care);
+
+        if covers(others(), cube) {
+            continue;
+        }
+
+        for bit in (0..variables).map(|b| 1 << b) {
+            if cube.care() & bit != 0 {
+                continue;
+            }
+
+            let one = Cube::new(cube.value() | bit, cube.care() | bit);
+            let zero = Cube::new(cube.value(), cube.care() | bit);
+
+            if covers(others(), one) {
+                cube = zero;
+            } else if covers(others(), zero) {
+                cube = one;
+            }
+        }
+
+        cover.insert(i, cube);
+        i += 1;
+    }
+}
+
+// Whether `inner` lies within `outer`
+fn contains(outer: Cube, inner: Cube) -> bool {
+    outer.care() & !inner.care() == 0 && inner.value() & outer.care() == outer.value()
+}
+
+fn intersects(a: Cube, b: Cube) -> bool {
+    (a.value() ^ b.value()) & a.care() & b.care() == 0
+}
+
+// Whether the cubes together contain `cube`: their cofactor by it is a tautology
+fn covers<'a>(cover: impl IntoIterator<Item = &'a Cube>, cube: Cube) -> bool {
+    tautology(cofactor(cover, cube))
+}
+
+// The cubes restricted to `cube`, its variables left out
+fn cofactor<'a>(cover: impl IntoIterator<Item = &'a Cube>, cube: Cube) -> Vec<Cube> {
+    cover
+        .into_iter()
+        .filter(|&&other| intersects(other, cube))
+        .map(|other| Cube::new(other.value(), other.care() & !cube.care()))
+        .collect()
+}
+
+// Split on the most frequent variable occurring both negated and not. Without one
+// the cover is unate, and only a tautology if it holds the universal cube.
+fn tautology(cover: Vec<Cube>) -> bool {
+    if cover.iter().any(|cube| cube.care() == 0) {
+        return true;
+    }
+
+    let split = (0..64)
+        .map(|b| 1 << b)
+        .filter_map(|bit| {
+            let cared = cover.iter().filter(|cube| cube.care() & bit != 0);
+            let positive = cared.clone().filter(|cube| cube.value() & bit != 0).count();
+            let negative = cared.count() - positive;
+
+            (positive > 0 && negative > 0).then_some((positive + negative, bit))
+        })
+        .max();
+
+    match split {
+        Some((_, bit)) => [0, bit]
+            .into_iter()
+            .all(|value| tautology(cofactor(&cover, Cube::new(value, bit)))),
+        None => false,
+    }
+}
+
+// Every cube in both covers
+fn product(a: &[Cube], b: &[Cube]) -> Vec<Cube> {
+    let cubes = a.iter().flat_map(|&x| {
+        b.iter()
+            .filter(move |&&y| intersects(x, y))
+            .map(move |y| Cube::new(x.value() | y.value(), x.care() | y.care()))
+    });
+
+    absorb(cubes.collect())
+}
+
+// Without the cubes another one contains
+fn absorb(mut cubes: Vec<Cube>) -> Vec<Cube> {
+    cubes.sort_by_key(|cube| (cube.literals(), *cube));
+    cubes.dedup();
+
+    let mut kept: Vec<Cube> = Vec::new();
+
+    for cube in cubes {
+        if !kept.iter().any(|&other| contains(other, cube)) {
+            kept.push(cube);
+        }
+    }
+
+    kept
+}
+
+impl Node {
+    // A cover of the formula over `variables`, put together from the covers of its
+    // operands. Products start from the smallest cover and stop once empty, so a
+    // contradiction is never multiplied out.
+    pub fn to_cubes(&self, variables: &[Variable]) -> Result<Vec<Cube>, Error> {
+        match variables.len() {
+            0..=64 => Ok(self.cover(variables, true)),
+            n => Err(Error::TooManyVariables(n)),
+        }
+    }
+
+    // A near-minimal formula of the same meaning, where `minimize` is out of reach
+    pub fn espresso(&self, form: Form) -> Result<Self, Error> {
+        self.espresso_with(&Node::new(Clause::Value(false), None, None), form)
+    }
+
+    // As `espresso`, free to go either way where `dont_care` holds
+    pub fn espresso_with(&self, dont_care: &Node, form: Form) -> Result<Self, Error> {
+        let mut variables = self.variables();
+
+        variables.extend(dont_care.variables());
+        variables.sort();
+        variables.dedup();
+
+        let on = match form {
+            Form::SumOfProducts => self.to_cubes(&variables)?,
+            // The rows where the formula does not hold
+            Form::ProductOfSums => Node::new(Clause::Negation, Some(Box::new(self.clone())), None)
+                .to_cubes(&variables)?,
+        };
+        let dont_care = dont_care.to_cubes(&variables)?;
+
+        Ok(Self::from_cubes(
+            &variables,
+            &espresso(variables.len(), &on, &dont_care)?,
+            form,
+        ))
+    }
+
+    // The cubes where the formula holds, or where it does not
+    fn cover(&self, variables: &[Variable], positive: bool) -> Vec<Cube> {
+        let covers = |operator| -> Vec<Vec<Cube>> {
+            self.flatten(operator)
+                .into_iter()
+                .map(|node| node.cover(variables, positive))
+                .collect()
+        };
+        let both = |left: bool, right: bool| {
+            product(
+                &self.left().cover(variables, left),
+                &self.right().cover(variables, right),
+            )
+        };
+
+        match (self.clause, positive) {
+            (Clause::Variable(v), _) => {
+                let i = variables.iter().position(|&w| w == v).unwrap();
+                let bit = 1 << (variables.len() - 1 - i);
+
+                vec![Cube::new(if positive { bit } else { 0 }, bit)]
+            }
+            (Clause::Value(b), _) if b == positive => vec![Cube::new(0, 0)],
+            (Clause::Value(_), _) => vec![],
+            (Clause::Negation, _) => self.left().cover(variables, !positive),
+            (Clause::Conjunction, true) | (Clause::Disjunction, false) => {
+                let mut covers = covers(self.clause);
+                let mut cover = vec![Cube::new(0, 0)];
+
+                covers.sort_by_key(Vec::len);
+
+                for other in covers {
+                    if cover.is_empty() {
+                        break;
+                    }
+
+                    cover = product(&cover, &other);
+                }
+
+                cover
+            }
+            (Clause::Disjunction, true) | (Clause::Conjunction, false) => {
+                absorb(covers(self.clause).concat())
+            }
+            // ¬A ∨ B
+            (Clause::Material, true) => absorb(
+                [
+                    self.left().cover(variables, false),
+                    self.right().cover(variables, true),
+                ]
+                .concat(),
+            ),
+            (Clause::Material, false) => both(true, false),
+            // (A ∧ ¬B) ∨ (¬A ∧ B)
+            (Clause::Exclusive, true) | (Clause::Equivalence, false) => {
+                absorb([both(true, false), both(false, true)].concat())
+            }
+            // (A ∧ B) ∨ (¬A ∧ ¬B)
+            (Clause::Exclusive, false) | (Clause::Equivalence, true) => {
+                absorb([both(true, true), both(false, false)].concat())
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        error::Error,
+        node::{
+            assignment::Assignment,
+            espresso::*,
+            minimize::{Cube, Form},
+            *,
+        },
+    };
+
+    #[test]
+    fn consensus() {
+        let node = Node::from_infix("X & Y | !X & Z | Y & Z").unwrap();
+
+        assert_eq!(
+            node.espresso(Form::SumOfProducts).unwrap().formula(),
+            "X!Z&XY&|"
+        );
+        assert_equivalent(&node, &node.espresso(Form::ProductOfSums).unwrap());
+    }
+
+    #[test]
+    fn dont_cares() {
+        // f(A, B, C, D) = Σm(4, 8, 10, 11, 12, 15) + d(9, 14)
+        let cube = |m| Cube::minterm(m, 4);
+        let on = [4, 8, 10, 11, 12, 15].map(cube);
+        let cover = espresso(4, &on, &[9, 14].map(cube)).unwrap();
+
+        assert_eq!(cover.len(), 3);
+        assert_eq!(cover.iter().map(Cube::literals).sum::<u32>(), 7);
+        assert_eq!(espresso(65, &on, &[]), Err(Error::TooManyVariables(65)));
+
+        // The same, as formulas
+        let node = Node::from_infix("B & !C & !D | A & !B & !D | A & !B & C | A & C & D").unwrap();
+        let dont_care = Node::from_infix("A & !B & !C & D | A & B & C & !D").unwrap();
+
+        for form in [Form::SumOfProducts, Form::ProductOfSums] {
+            let minimal = node.espresso_with(&dont_care, form).unwrap();
+            let variables = node.variables();
+
+            println!("{minimal}");
+
+            for row in 0..16 {
+                let assignment = Assignment::from_bits(&variables, row);
+
+                if !dont_care.evaluate_under(&assignment).unwrap() {
+                    assert_eq!(
+                        minimal.evaluate_under(&assignment),
+                        node.evaluate_under(&assignment)
+                    );
+                }
+            }
+        }
+
+        assert_eq!(
+            node.espresso_with(&dont_care, Form::SumOfProducts)
+                .unwrap()
+                .formula(),
+            "BC!D!&&AB!&AC&||"
+        );
+    }
+
+    #[test]
+    fn equivalent() {
+        for formula in [
+            "AB&C|",
+            "AB^C^",
+            "AB>C=",
+            "AB|C&D!|",
+            "ABCD&|&",
+            "AB=CD=&",
+            "AB!&C|C!&",
+            "AA!&",
+            "AA!|",
+            "AB^C^D^E^",
+        ] {
+            let node: Node = formula.parse().unwrap();
+
+            for form in [Form::SumOfProducts, Form::ProductOfSums] {
+                let minimal = node.espresso(form).unwrap();
+
+                println!("{formula} {minimal}");
+
+                assert_equivalent(&node, &minimal);
+                assert_eq!(terms(&minimal, form), terms(&node.minimize(form), form));
+            }
+        }
+    }
+
+    // 39 inputs, a truth table of half a trillion rows
+    #[test]
+    fn large() {
+        let products: Vec<String> = (0..13)
+            .map(|i| format!("x{i} & y{i} | !x{i} & z{i} | y{i} & z{i} | x{i} & y{i} & z{i}"))
+            .collect();
+        let node = Node::from_infix(&products.join(" | ")).unwrap();
+        let minimal = node.espresso(Form::SumOfProducts).unwrap();
+
+        assert_eq!(terms(&minimal, Form::SumOfProducts), 26);
+        assert_equivalent(&node, &minimal);
+    }
+
+    // Distributing would multiply out 2^30 products before finding none survive
+    #[test]
+    fn contradiction() {
+        let clauses: Vec<String> = (0..30).map(|i| format!("(a{i} | b{i})")).collect();
+        let node = Node::from_infix(&format!("x & !x & {}", clauses.join(" & "))).unwrap();
+
+        assert_eq!(node.espresso(Form::SumOfProducts).unwrap().formula(), "0");
+        assert_eq!(node.espresso(Form::ProductOfSums).unwrap().formula(), "0");
+    }
+
+    #[test]
+    fn too_many_variables() {
+        let names: Vec<String> = (0..66).map(|i| format!("x{i}")).collect();
+        let node = Node::from_infix(&names.join(" | ")).unwrap();
+
+        assert_eq!(
+            node.espresso(Form::SumOfProducts),
+            Err(Error::TooManyVariables(66))
+        );
+    }
+
+    fn terms(node: &Node, form: Form) -> usize {
+        match form {
+            Form::SumOfProducts => node.flatten(Clause::Disjunction).len(),
+            Form::ProductOfSums => node.flatten(Clause::Conjunction).len(),
+        }
+    }
+
+    fn assert_equivalent(a: &Node, b: &Node) {
+        let differ = Node::new(
+            Clause::Exclusive,
+            Some(Box::new(a.clone())),
+            Some(Box::new(b.clone())),
+        );
+
+        assert!(!differ.sat());
+    }
+}
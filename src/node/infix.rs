@@ -0,0 +1,333 @@
+use std::{cmp::Ordering, iter::Peekable, vec::IntoIter};
+
+use super::{
+    Clause, Node,
+    error::{ParseError, ParseErrorKind},
+    variable::Variable,
+};
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Style {
+    Ascii,
+    Unicode,
+    Latex,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum Token {
+    Clause(Clause),
+    Open,
+    Close,
+}
+
+// A token, its position and its first character
+type Lexeme = (Token, usize, char);
+
+impl Node {
+    // Precedence from tight to loose: ¬, ∧, ⊕, ∨, ⇒, ⇔
+    pub fn from_infix(formula: &str) -> Result<Self, ParseError> {
+        let mut parser = Parser {
+            tokens: tokenize(formula)?.into_iter().peekable(),
+            last: None,
+        };
+
+        if parser.tokens.peek().is_none() {
+            return Err(ParseError::new(ParseErrorKind::EmptyInput, 0, None));
+        }
+
+        let root = parser.expression(0)?;
+
+        match parser.tokens.next() {
+            None => Ok(root),
+            Some((Token::Close, position, c)) => Err(ParseError::new(
+                ParseErrorKind::UnbalancedParenthesis,
+                position,
+                Some(c),
+            )),
+            Some((_, position, c)) => Err(ParseError::new(
+                ParseErrorKind::TrailingOperands,
+                position,
+                Some(c),
+            )),
+        }
+    }
+
+    // Only the parentheses needed to keep the tree's shape are printed
+    pub fn infix(&self, style: Style) -> String {
+        let symbol = spell(self.clause, style);
+        let right = self.clause.is_right_associative();
+
+        match self.clause {
+            Clause::Value(_) | Clause::Variable(_) => symbol,
+            Clause::Negation => {
+                format!("{symbol}{}", self.left().operand(self.clause, style, false))
+            }
+            _ => format!(
+                "{} {symbol} {}",
+                self.left().operand(self.clause, style, right),
+                self.right().operand(self.clause, style, !right),
+            ),
+        }
+    }
+
+    // `tie` is whether an operand of equal precedence needs parentheses
+    fn operand(&self, parent: Clause, style: Style, tie: bool) -> String {
+        let infix = self.infix(style);
+
+        match Ord::cmp(&self.clause.precedence(), &parent.precedence()) {
+            Ordering::Less => format!("({infix})"),
+            Ordering::Equal if tie && !self.clause.is_operand() => format!("({infix})"),
+            _ => infix,
+        }
+    }
+}
+
+fn spell(clause: Clause, style: Style) -> String {
+    match (style, clause) {
+        (Style::Unicode, _) | (_, Clause::Variable(_)) => clause.symbol(),
+        (Style::Ascii, Clause::Material) => "->".to_string(),
+        (Style::Ascii, Clause::Equivalence) => "<->".to_string(),
+        (Style::Ascii, _) => clause.to(),
+        (Style::Latex, Clause::Value(false)) => "\\bot".to_string(),
+        (Style::Latex, Clause::Value(true)) => "\\top".to_string(),
+        (Style::Latex, Clause::Negation) => "\\neg ".to_string(),
+        (Style::Latex, Clause::Conjunction) => "\\land".to_string(),
+        (Style::Latex, Clause::Disjunction) => "\\lor".to_string(),
+        (Style::Latex, Clause::Exclusive) => "\\oplus".to_string(),
+        (Style::Latex, Clause::Material) => "\\Rightarrow".to_string(),
+        (Style::Latex, Clause::Equivalence) => "\\Leftrightarrow".to_string(),
+    }
+}
+
+fn tokenize(formula: &str) -> Result<Vec<Lexeme>, ParseError> {
+    let chars: Vec<char> = formula.chars().collect();
+    let mut tokens = Vec::new();
+    let mut i = 0;
+
+    while i < chars.len() {
+        let c = chars[i];
+        let rest = &chars[i..];
+
+        if c.is_whitespace() {
+            i += 1;
+            continue;
+        }
+
+        let (token, len) = match c {
+            '(' => (Token::Open, 1),
+            ')' => (Token::Close, 1),
+            '-' if rest.starts_with(&['-', '>']) => (Token::Clause(Clause::Material), 2),
+            '<' if rest.starts_with(&['<', '-', '>']) => (Token::Clause(Clause::Equivalence), 3),
+            _ if c.is_ascii_alphabetic() || c == '_' => {
+                let len = rest
+                    .iter()
+                    .take_while(|c| c.is_ascii_alphanumeric() || **c == '_')
+                    .count();
+                let name: String = rest[..len].iter().collect();
+
+                (Token::Clause(Clause::Variable(Variable::new(&name))), len)
+            }
+            '>' | '=' => {
+                return Err(ParseError::new(ParseErrorKind::UnknownSymbol, i, Some(c)));
+            }
+            _ => match Clause::from_symbol(c).or(Clause::from(c)) {
+                Some(clause) => (Token::Clause(clause), 1),
+                None => {
+                    return Err(ParseError::new(ParseErrorKind::UnknownSymbol, i, Some(c)));
+                }
+            },
+        };
+
+        tokens.push((token, i, c));
+        i += len;
+    }
+
+    Ok(tokens)
+}
+
+struct Parser {
+    tokens: Peekable<IntoIter<Lexeme>>,
+    // The last token consumed, blamed when the formula ends too early
+    last: Option<Lexeme>,
+}
+
+impl Parser {
+    fn next(&mut self) -> Option<Lexeme> {
+        let next = self.tokens.next();
+
+        if next.is_some() {
+            self.last = next;
+        }
+
+        next
+    }
+
+    // Precedence climbing over the binary operators
+    fn expression(&mut self, min: u8) -> Result<Node, ParseError> {
+        let mut left = self.operand()?;
+
+        while let Some(&(Token::Clause(clause), ..)) = self.tokens.peek() {
+            if clause.is_operand() || clause.precedence() < min {
+                break;
+            }
+
+            self.next();
+
+            let next = match clause.is_right_associative() {
+                true => clause.precedence(),
+                false => clause.precedence() + 1,
+            };
+            let right = self.expression(next)?;
+
+            left = Node::new(clause, Some(Box::new(left)), Some(Box::new(right)));
+        }
+
+        Ok(left)
+    }
+
+    fn operand(&mut self) -> Result<Node, ParseError> {
+        let Some((token, position, c)) = self.next() else {
+            let (_, position, c) = self.last.unwrap();
+
+            return Err(ParseError::new(
+                ParseErrorKind::MissingOperand,
+                position,
+                Some(c),
+            ));
+        };
+
+        match token {
+            Token::Clause(clause) if clause.is_operand() => Ok(Node::new(clause, None, None)),
+            Token::Clause(Clause::Negation) => {
+                let left = self.operand()?;
+
+                Ok(Node::new(Clause::Negation, Some(Box::new(left)), None))
+            }
+            Token::Open => {
+                let node = self.expression(0)?;
+
+                match self.next() {
+                    Some((Token::Close, ..)) => Ok(node),
+                    _ => Err(ParseError::new(
+                        ParseErrorKind::UnbalancedParenthesis,
+                        position,
+                        Some(c),
+                    )),
+                }
+            }
+            _ => Err(ParseError::new(
+                ParseErrorKind::UnexpectedSymbol,
+                position,
+                Some(c),
+            )),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::node::{error::*, infix::*, *};
+
+    #[test]
+    fn ascii() {
+        compare("A", "A");
+        compare("!A", "A!");
+        compare("!!A", "A!!");
+        compare("A & B", "AB&");
+        compare("A | B & C", "ABC&|");
+        compare("A & B | C", "AB&C|");
+        compare("A ^ B | C & D", "AB^CD&|");
+        compare("(A & B) -> !C", "AB&C!>");
+        compare("A -> B -> C", "ABC>>");
+        compare("A & B & C", "AB&C&");
+        compare("A <-> B -> C | 0", "ABC0|>=");
+        compare("!(A|B)", "AB|!");
+        compare("((A))", "A");
+        compare("x_12 & carry3 | a", "{x_12}{carry3}&{a}|");
+        compare("AB", "{AB}");
+    }
+
+    #[test]
+    fn unicode() {
+        compare("A ∧ ¬B ⇔ C", "AB!&C=");
+        compare("A ⊕ B ⇒ ⊤ ∨ ⊥", "AB^10|>");
+    }
+
+    #[test]
+    fn invalid() {
+        error("", ParseErrorKind::EmptyInput, 0, None);
+        error("   ", ParseErrorKind::EmptyInput, 0, None);
+        error("A # B", ParseErrorKind::UnknownSymbol, 2, Some('#'));
+        error("A - B", ParseErrorKind::UnknownSymbol, 2, Some('-'));
+        error("A > B", ParseErrorKind::UnknownSymbol, 2, Some('>'));
+        error("A &", ParseErrorKind::MissingOperand, 2, Some('&'));
+        error("!", ParseErrorKind::MissingOperand, 0, Some('!'));
+        error("A B", ParseErrorKind::TrailingOperands, 2, Some('B'));
+        error("2A", ParseErrorKind::UnknownSymbol, 0, Some('2'));
+        error("A & | B", ParseErrorKind::UnexpectedSymbol, 4, Some('|'));
+        error(
+            "(A & B",
+            ParseErrorKind::UnbalancedParenthesis,
+            0,
+            Some('('),
+        );
+        error(
+            "A & B)",
+            ParseErrorKind::UnbalancedParenthesis,
+            5,
+            Some(')'),
+        );
+        error("()", ParseErrorKind::UnexpectedSymbol, 1, Some(')'));
+    }
+
+    #[test]
+    fn render() {
+        compare_infix("AB&C!>", "A & B -> !C", "A ∧ B ⇒ ¬C");
+        compare_infix("ABC&|", "A | B & C", "A ∨ B ∧ C");
+        compare_infix("AB|C&", "(A | B) & C", "(A ∨ B) ∧ C");
+        compare_infix("AB&C&", "A & B & C", "A ∧ B ∧ C");
+        compare_infix("ABC&&", "A & (B & C)", "A ∧ (B ∧ C)");
+        compare_infix("ABC>>", "A -> B -> C", "A ⇒ B ⇒ C");
+        compare_infix("AB>C>", "(A -> B) -> C", "(A ⇒ B) ⇒ C");
+        compare_infix("AB|!!", "!!(A | B)", "¬¬(A ∨ B)");
+        compare_infix("AB^C=0!1|=", "A ^ B <-> C <-> !0 | 1", "A ⊕ B ⇔ C ⇔ ¬⊥ ∨ ⊤");
+    }
+
+    #[test]
+    fn latex() {
+        let node: Node = "AB&C!>D=".parse().unwrap();
+
+        assert_eq!(
+            node.infix(Style::Latex),
+            "A \\land B \\Rightarrow \\neg C \\Leftrightarrow D"
+        );
+    }
+
+    fn compare_infix(rpn: &str, ascii: &str, unicode: &str) {
+        let node: Node = rpn.parse().unwrap();
+
+        println!("{rpn}: {node:#}");
+
+        assert_eq!(node.infix(Style::Ascii), ascii);
+        assert_eq!(node.infix(Style::Unicode), unicode);
+        assert_eq!(format!("{node:#}"), unicode);
+        assert_eq!(Node::from_infix(ascii).unwrap().formula(), rpn);
+        assert_eq!(Node::from_infix(unicode).unwrap().formula(), rpn);
+    }
+
+    fn compare(infix: &str, rpn: &str) {
+        let node = Node::from_infix(infix).unwrap();
+
+        println!("{infix}: {node}");
+
+        assert_eq!(node.formula(), rpn);
+    }
+
+    fn error(infix: &str, kind: ParseErrorKind, position: usize, symbol: Option<char>) {
+        let error = Node::from_infix(infix).unwrap_err();
+
+        println!("{infix}: {error}");
+
+        assert_eq!(error, ParseError::new(kind, position, symbol));
+    }
+}
@@ -0,0 +1,412 @@
+use std::collections::{BTreeMap, BTreeSet};
+
+use super::{Clause, Node, variable::Variable};
+use crate::error::Error;
+
+// Which two-level form `minimize` produces
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Form {
+    SumOfProducts,
+    ProductOfSums,
+}
+
+// A product of literals over n variables, numbered like the rows of a truth table:
+// the first variable is the most significant bit. A variable takes part if its bit
+// of `care` is set, and is negated if its bit of `value` is clear.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Cube {
+    value: u64,
+    care: u64,
+}
+
+impl Cube {
+    // Bits of `value` outside of `care` are ignored
+    pub fn new(value: u64, care: u64) -> Self {
+        Self {
+            value: value & care,
+            care,
+        }
+    }
+
+    // The single row `minterm` of a table over n variables
+    pub fn minterm(minterm: u64, variables: usize) -> Self {
+        let care = mask(variables);
+
+        Self {
+            value: minterm & care,
+            care,
+        }
+    }
+
+    pub fn value(&self) -> u64 {
+        self.value
+    }
+
+    pub fn care(&self) -> u64 {
+        self.care
+    }
+
+    pub fn literals(&self) -> u32 {
+        self.care.count_ones()
+    }
+
+    pub fn contains(&self, minterm: u64) -> bool {
+        minterm & self.care == self.value
+    }
+
+    // AB + A¬B = A
+    fn merge(self, other: Self) -> Option<Self> {
+        let diff = self.value ^ other.value;
+
+        match self.care == other.care && diff.count_ones() == 1 {
+            true => Some(Self {
+                value: self.value & !diff,
+                care: self.care & !diff,
+            }),
+            false => None,
+        }
+    }
+
+    // A conjunction of the literals, or a disjunction of their negations
+    fn node(&self, variables: &[Variable], form: Form) -> Box<Node> {
+        let n = variables.len();
+        let literals = (0..n)
+            .filter(|i| self.care >> (n - 1 - i) & 1 == 1)
+            .map(|i| {
+                let positive = self.value >> (n - 1 - i) & 1 == 1;
+
+                match form {
+                    Form::SumOfProducts => Node::literal(variables[i], positive),
+                    Form::ProductOfSums => Node::literal(variables[i], !positive),
+                }
+            });
+
+        match form {
+            Form::SumOfProducts => Node::chain(Clause::Conjunction, true, literals),
+            Form::ProductOfSums => Node::chain(Clause::Disjunction, false, literals),
+        }
+    }
+}
+
+// Quine–McCluskey: merge cubes differing in a single variable until none do.
+// Cubes that never merge are prime.
+pub fn prime_implicants(variables: usize, minterms: &[u64], dont_cares: &[u64]) -> Vec<Cube> {
+    let mut cubes: BTreeSet<Cube> = minterms
+        .iter()
+        .chain(dont_cares)
+        .map(|&m| Cube::minterm(m, variables))
+        .collect();
+    let mut primes = BTreeSet::new();
+
+    while !cubes.is_empty() {
+        // Only cubes with the same variables and one more positive literal can merge
+        let mut groups: BTreeMap<(u64, u32), Vec<Cube>> = BTreeMap::new();
+        let mut merged = BTreeSet::new();
+        let mut used = BTreeSet::new();
+
+        for &cube in &cubes {
+            groups
+                .entry((cube.care, cube.value.count_ones()))
+                .or_default()
+                .push(cube);
+        }
+
+        for (&(care, ones), group) in &groups {
+            let Some(next) = groups.get(&(care, ones + 1)) else {
+                continue;
+            };
+
+            for &a in group {
+                for &b in next {
+                    if let Some(cube) = a.merge(b) {
+                        merged.insert(cube);
+                        used.insert(a);
+                        used.insert(b);
+                    }
+                }
+            }
+        }
+
+        primes.extend(cubes.difference(&used).copied());
+        cubes = merged;
+    }
+
+    primes.into_iter().collect()
+}
+
+// The fewest implicants covering every minterm, ties broken by the fewest literals.
+// Essential implicants are taken first, Petrick's method picks among the rest.
+pub fn minimum_cover(implicants: &[Cube], minterms: &[u64]) -> Vec<Cube> {
+    let covering = |minterm: u64| {
+        (0..implicants.len())
+            .filter(move |&i| implicants[i].contains(minterm))
+            .collect::<Vec<usize>>()
+    };
+    let mut chosen = BTreeSet::new();
+
+    for &minterm in minterms {
+        if let [i] = covering(minterm)[..] {
+            chosen.insert(i);
+        }
+    }
+
+    let remaining = minterms
+        .iter()
+        .copied()
+        .filter(|&minterm| !chosen.iter().any(|&i| implicants[i].contains(minterm)));
+
+    // The product of sums of implicants covering each minterm, multiplied out.
+    // X(X + Y) = X, and XY is dropped next to X.
+    let mut products: Vec<BTreeSet<usize>> = vec![BTreeSet::new()];
+
+    for minterm in remaining {
+        let sum = covering(minterm);
+        let mut next: Vec<BTreeSet<usize>> = Vec::new();
+
+        for product in products {
+            match sum.iter().any(|i| product.contains(i)) {
+                true => next.push(product),
+                false => next.extend(sum.iter().map(|&i| {
+                    let mut product = product.clone();
+
+                    product.insert(i);
+                    product
+                })),
+            }
+        }
+
+        next.sort_by_key(BTreeSet::len);
+        next.dedup();
+        products = Vec::new();
+
+        for product in next {
+            if !products.iter().any(|other| other.is_subset(&product)) {
+                products.push(product);
+            }
+        }
+    }
+
+    let literals = |product: &BTreeSet<usize>| {
+        product
+            .iter()
+            .map(|&i| implicants[i].literals())
+            .sum::<u32>()
+    };
+    let best = products
+        .into_iter()
+        .min_by_key(|product| (product.len(), literals(product)))
+        .unwrap_or_default();
+
+    chosen
+        .into_iter()
+        .chain(best)
+        .map(|i| implicants[i])
+        .collect::<BTreeSet<_>>()
+        .into_iter()
+        .collect()
+}
+
+fn mask(variables: usize) -> u64 {
+    match variables {
+        64.. => u64::MAX,
+        n => (1 << n) - 1,
+    }
+}
+
+impl Node {
+    // The rows of the truth table where the formula holds
+    pub fn minterms(&self) -> Vec<u64> {
+        let variables = self.variables();
+        let n = variables.len();
+
+        (0..1 << n)
+            .filter(|&row: &u64| {
+                self.evaluate_with(|v| {
+                    let i = variables.binary_search(&v).unwrap();
+
+                    row >> (n - 1 - i) & 1 == 1
+                })
+            })
+            .collect()
+    }
+
+    // A minimal formula of the same truth table
+    pub fn minimize(&self, form: Form) -> Self {
+        Self::from_minterms(&self.variables(), &self.minterms(), &[], form)
+            .expect("the formula's own minterms are rows of its table")
+    }
+
+    // A minimal formula over `variables` that holds on `minterms` and on none of the
+    // other rows, save for `dont_cares`, which may go either way. Every row has to
+    // be one of the table's.
+    pub fn from_minterms(
+        variables: &[Variable],
+        minterms: &[u64],
+        dont_cares: &[u64],
+        form: Form,
+    ) -> Result<Self, Error> {
+        let n = variables.len();
+
+        if n > 64 {
+            return Err(Error::TooManyVariables(n));
+        }
+
+        if let Some(&row) = minterms
+            .iter()
+            .chain(dont_cares)
+            .find(|&&row| row > mask(n))
+        {
+            return Err(Error::MintermOutOfRange(row));
+        }
+
+        let cover = match form {
+            Form::SumOfProducts => minterms.to_vec(),
+            // ¬f as a sum of products, negated
+            Form::ProductOfSums => {
+                let specified: BTreeSet<u64> = minterms.iter().chain(dont_cares).copied().collect();
+
+                (0..=mask(n))
+                    .filter(|row| !specified.contains(row))
+                    .collect()
+            }
+        };
+        let implicants = prime_implicants(n, &cover, dont_cares);
+
+        Ok(Self::from_cubes(
+            variables,
+            &minimum_cover(&implicants, &cover),
+            form,
+        ))
+    }
+
+    // The sum of the cubes, or the product of their negations: cubes then cover
+    // the rows where the formula does not hold
+    pub fn from_cubes(variables: &[Variable], cubes: &[Cube], form: Form) -> Self {
+        let terms = cubes.iter().map(|cube| cube.node(variables, form));
+
+        match form {
+            Form::SumOfProducts => *Node::chain(Clause::Disjunction, false, terms),
+            Form::ProductOfSums => *Node::chain(Clause::Conjunction, true, terms),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::node::{assignment::Assignment, minimize::*, *};
+
+    #[test]
+    fn primes() {
+        // f(A, B, C, D) = Σm(4, 8, 10, 11, 12, 15) + d(9, 14)
+        let primes = prime_implicants(4, &[4, 8, 10, 11, 12, 15], &[9, 14]);
+
+        assert_eq!(primes.len(), 4);
+
+        let cover = minimum_cover(&primes, &[4, 8, 10, 11, 12, 15]);
+
+        assert_eq!(cover.len(), 3);
+        assert_eq!(cover.iter().map(Cube::literals).sum::<u32>(), 7);
+    }
+
+    #[test]
+    fn petrick() {
+        // Cyclic: no implicant is essential
+        let primes = prime_implicants(3, &[0, 1, 2, 5, 6, 7], &[]);
+
+        assert_eq!(primes.len(), 6);
+        assert_eq!(minimum_cover(&primes, &[0, 1, 2, 5, 6, 7]).len(), 3);
+    }
+
+    #[test]
+    fn minimize() {
+        compare("AB&AB!&|", Form::SumOfProducts, "A");
+        compare("AB|A!B|&", Form::ProductOfSums, "B");
+        compare("AB&A!C&|BC&|", Form::SumOfProducts, "A!C&AB&|");
+        compare("AB|A!C|&BC|&", Form::ProductOfSums, "AB|A!C|&");
+        compare("AB^", Form::SumOfProducts, "A!B&AB!&|");
+        compare("AB^", Form::ProductOfSums, "AB|A!B!|&");
+        compare("AA!&", Form::SumOfProducts, "0");
+        compare("AA!|", Form::SumOfProducts, "1");
+        compare("AA!&", Form::ProductOfSums, "0");
+        compare("AA!|", Form::ProductOfSums, "1");
+    }
+
+    #[test]
+    fn dont_cares() {
+        let variables = ['A', 'B', 'C', 'D'].map(Variable::from);
+        let minterms = [4, 8, 10, 11, 12, 15];
+        let dont_cares = [9, 14];
+
+        for form in [Form::SumOfProducts, Form::ProductOfSums] {
+            let node = Node::from_minterms(&variables, &minterms, &dont_cares, form).unwrap();
+
+            println!("{node:#}");
+
+            // Over all four variables, whichever of them the result still uses
+            for row in 0..16 {
+                let holds = node
+                    .evaluate_under(&Assignment::from_bits(&variables, row))
+                    .unwrap();
+
+                if minterms.contains(&row) {
+                    assert!(holds);
+                } else if !dont_cares.contains(&row) {
+                    assert!(!holds);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn errors() {
+        let variables = ['A', 'B'].map(Variable::from);
+
+        for form in [Form::SumOfProducts, Form::ProductOfSums] {
+            assert_eq!(
+                Node::from_minterms(&variables, &[1, 4], &[], form),
+                Err(Error::MintermOutOfRange(4))
+            );
+            assert_eq!(
+                Node::from_minterms(&variables, &[1], &[7], form),
+                Err(Error::MintermOutOfRange(7))
+            );
+        }
+
+        let variables: Vec<Variable> = (0..65).map(|i| Variable::new(&format!("x{i}"))).collect();
+
+        assert_eq!(
+            Node::from_minterms(&variables, &[], &[], Form::SumOfProducts),
+            Err(Error::TooManyVariables(65))
+        );
+    }
+
+    #[test]
+    fn equivalent() {
+        for formula in [
+            "AB&C|",
+            "AB^C^",
+            "AB>C=",
+            "AB|C&D!|",
+            "ABCD&|&",
+            "AB=CD=&",
+            "AB!&C|C!&",
+            "AB&AB!&|C&",
+        ] {
+            let node: Node = formula.parse().unwrap();
+
+            for form in [Form::SumOfProducts, Form::ProductOfSums] {
+                let minimal = node.minimize(form);
+
+                println!("{formula} {minimal}");
+
+                assert_eq!(minimal.equivalent(&node), Ok(()));
+            }
+        }
+    }
+
+    fn compare(formula: &str, form: Form, expected: &str) {
+        let node: Node = formula.parse().unwrap();
+
+        assert_eq!(node.minimize(form).formula(), expected);
+    }
+}
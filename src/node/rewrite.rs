@@ -23,6 +23,34 @@ impl Node {
         }
     }
 
+    pub fn to_dnf(&mut self) {
+        self.foreach_mut(Self::implies);
+        self.foreach_mut(Self::disjunctive_negation);
+
+        while !self.is_dnf() {
+            self.foreach_mut(Self::dual_distributivity);
+        }
+    }
+
+    // The conversions above, without the redundancy `reduce` removes
+    pub fn to_nnf_reduced(&mut self) {
+        self.reduce();
+        self.to_nnf();
+        self.reduce();
+    }
+
+    pub fn to_cnf_reduced(&mut self) {
+        self.reduce();
+        self.to_cnf();
+        self.reduce();
+    }
+
+    pub fn to_dnf_reduced(&mut self) {
+        self.reduce();
+        self.to_dnf();
+        self.reduce();
+    }
+
     pub fn is_nnf(&self) -> bool {
         match self.clause {
             Clause::Value(_) | Clause::Variable(_) => true,
@@ -44,6 +72,18 @@ impl Node {
         }
     }
 
+    pub fn is_dnf(&self) -> bool {
+        match self.clause {
+            Clause::Value(_) | Clause::Variable(_) => true,
+            Clause::Negation => self.left().clause.is_operand(),
+            Clause::Disjunction => self.children().all(Node::is_dnf),
+            Clause::Conjunction => self
+                .children()
+                .all(|node| node.clause != Clause::Disjunction && node.is_dnf()),
+            _ => false,
+        }
+    }
+
     // Remove ⇔, ⇒ and ⊕
     pub fn simplify(&mut self) {
         self.foreach_mut(Self::equivalence);
@@ -51,6 +91,127 @@ impl Node {
         self.foreach_mut(Self::exclusivity);
     }
 
+    // Identity, annihilation, idempotence, complementation, absorption and
+    // constant propagation, until none of them applies
+    pub fn reduce(&mut self) {
+        loop {
+            let before = self.clone();
+
+            self.reduce_once();
+
+            if *self == before {
+                break;
+            }
+        }
+    }
+
+    fn reduce_once(&mut self) {
+        for child in self.children_mut() {
+            child.reduce_once();
+        }
+
+        match self.clause {
+            Clause::Negation => match self.left().clause {
+                // ¬0 ⇔ 1, ¬1 ⇔ 0
+                Clause::Value(b) => *self = Node::value(!b),
+                // ¬¬A ⇔ A
+                Clause::Negation => self.double_negation(),
+                _ => (),
+            },
+            Clause::Conjunction | Clause::Disjunction => self.reduce_chain(),
+            Clause::Exclusive | Clause::Equivalence | Clause::Material => self.reduce_binary(),
+            _ => (),
+        }
+    }
+
+    // A chain of ∧ (∨), with its operands:
+    // A ∧ 0 ⇔ 0, A ∧ ¬A ⇔ 0, A ∧ 1 ⇔ A, A ∧ A ⇔ A, A ∧ (A ∨ B) ⇔ A
+    fn reduce_chain(&mut self) {
+        let operator = self.clause;
+        let (dual, annihilator) = match operator {
+            Clause::Conjunction => (Clause::Disjunction, false),
+            _ => (Clause::Conjunction, true),
+        };
+        let operands = self.flatten(operator);
+
+        if operands.iter().any(|node| {
+            node.clause == Clause::Value(annihilator)
+                || operands.iter().any(|other| node.is_complement(other))
+        }) {
+            *self = Node::value(annihilator);
+            return;
+        }
+
+        let factors: Vec<Vec<&Node>> = operands.iter().map(|node| node.flatten(dual)).collect();
+        let within = |i: usize, j: usize| factors[i].iter().all(|node| factors[j].contains(node));
+
+        // An operand goes if another one's factors are among its own. Of two with
+        // the same factors, the first stays.
+        let kept: Vec<Box<Node>> = (0..operands.len())
+            .filter(|&i| operands[i].clause != Clause::Value(!annihilator))
+            .filter(|&i| {
+                !(0..operands.len()).any(|j| j != i && within(j, i) && (j < i || !within(i, j)))
+            })
+            .map(|i| Box::new(operands[i].clone()))
+            .collect();
+
+        if kept.len() < operands.len() {
+            *self = *Node::chain(operator, !annihilator, kept);
+        }
+    }
+
+    // A ⊕ 0 ⇔ A, A ⊕ 1 ⇔ ¬A, A ⊕ A ⇔ 0, A ⊕ ¬A ⇔ 1
+    // A ⇔ 1 ⇔ A, A ⇔ 0 ⇔ ¬A, A ⇔ A ⇔ 1, A ⇔ ¬A ⇔ 0
+    // 0 ⇒ A ⇔ 1, 1 ⇒ A ⇔ A, A ⇒ 1 ⇔ 1, A ⇒ 0 ⇔ ¬A, A ⇒ A ⇔ 1, A ⇒ ¬A ⇔ ¬A
+    fn reduce_binary(&mut self) {
+        let (left, right) = (self.left(), self.right());
+        let constant = |node: &Node| match node.clause {
+            Clause::Value(b) => Some(b),
+            _ => None,
+        };
+        let negation =
+            |node: &Node| Node::new(Clause::Negation, Some(Box::new(node.clone())), None);
+
+        let reduced = match (self.clause, constant(left), constant(right)) {
+            (_, Some(_), Some(_)) => Node::value(self.evaluate()),
+            (Clause::Material, Some(false), _) | (Clause::Material, _, Some(true)) => {
+                Node::value(true)
+            }
+            (Clause::Material, Some(true), _) => right.clone(),
+            (Clause::Material, _, Some(false)) => negation(left),
+            (clause, Some(b), _) | (clause, _, Some(b)) => {
+                let other = match constant(left) {
+                    Some(_) => right,
+                    None => left,
+                };
+
+                match (clause == Clause::Exclusive) == b {
+                    true => negation(other),
+                    false => other.clone(),
+                }
+            }
+            (clause, None, None) if left == right => Node::value(clause != Clause::Exclusive),
+            (Clause::Material, None, None) if left.is_complement(right) => right.clone(),
+            (clause, None, None) if left.is_complement(right) => {
+                Node::value(clause == Clause::Exclusive)
+            }
+            _ => return,
+        };
+
+        *self = reduced;
+    }
+
+    // One is the other negated
+    fn is_complement(&self, other: &Node) -> bool {
+        let negates = |a: &Node, b: &Node| a.clause == Clause::Negation && a.left() == b;
+
+        negates(self, other) || negates(other, self)
+    }
+
+    fn value(b: bool) -> Self {
+        Node::new(Clause::Value(b), None, None)
+    }
+
     // (A ⇔ B) ⇔ ((A ⇒ B) ∧ (B ⇒ A))
     fn equivalence(&mut self) {
         if self.clause == Clause::Equivalence {
@@ -101,6 +262,75 @@ impl Node {
         }
     }
 
+    // Push negations down and remove ⇔ and ⊕ in a single pass, so the negation
+    // of an ⇔ or ⊕ never has to be distributed over its expansion
+    fn disjunctive_negation(&mut self) {
+        self.push_negation();
+
+        match self.clause {
+            // (A ⇔ B) ⇔ ((A ∧ B) ∨ (¬A ∧ ¬B))
+            Clause::Equivalence => self.expand(
+                Clause::Disjunction,
+                Clause::Conjunction,
+                [(false, false), (true, true)],
+            ),
+            // (A ⊕ B) ⇔ ((A ∧ ¬B) ∨ (¬A ∧ B))
+            Clause::Exclusive => self.expand(
+                Clause::Disjunction,
+                Clause::Conjunction,
+                [(false, true), (true, false)],
+            ),
+            _ => (),
+        }
+    }
+
+    // ¬(A ⊕ B) ⇔ (A ⇔ B)
+    // ¬(A ⇔ B) ⇔ (A ⊕ B)
+    fn push_negation(&mut self) {
+        self.double_negation();
+        self.de_morgan();
+
+        if self.clause == Clause::Negation
+            && matches!(self.left().clause, Clause::Exclusive | Clause::Equivalence)
+        {
+            let mut inner = self.left.take().unwrap();
+
+            inner.clause = match inner.clause {
+                Clause::Exclusive => Clause::Equivalence,
+                _ => Clause::Exclusive,
+            };
+
+            *self = *inner;
+        }
+    }
+
+    // Rewrite A ∘ B as (±A ∙ ±B) ⋄ (±A ∙ ±B), the signs saying which operands are negated
+    fn expand(&mut self, outer: Clause, inner: Clause, signs: [(bool, bool); 2]) {
+        let left = self.left.take().unwrap();
+        let right = self.right.take().unwrap();
+        let operand = |node: &Node, negated| match negated {
+            true => Box::new(Node::new(
+                Clause::Negation,
+                Some(Box::new(node.clone())),
+                None,
+            )),
+            false => Box::new(node.clone()),
+        };
+        let [(a, b), (c, d)] = signs;
+
+        self.clause = outer;
+        self.left = Some(Box::new(Node::new(
+            inner,
+            Some(operand(&left, a)),
+            Some(operand(&right, b)),
+        )));
+        self.right = Some(Box::new(Node::new(
+            inner,
+            Some(operand(&left, c)),
+            Some(operand(&right, d)),
+        )));
+    }
+
     // ¬(A ∨ B) ⇔ (¬A ∧ ¬B)
     // ¬(A ∧ B) ⇔ (¬A ∨ ¬B)
     fn de_morgan(&mut self) {
@@ -133,16 +363,21 @@ impl Node {
 
     // (A ∨ (B ∧ C)) ⇔ ((A ∨ B) ∧ (A ∨ C))
     fn distributivity(&mut self) {
-        if self.clause == Clause::Disjunction
-            && self
-                .children()
-                .any(|node| node.clause == Clause::Conjunction)
-        {
-            self.clause = Clause::Conjunction;
+        self.distribute(Clause::Disjunction, Clause::Conjunction);
+    }
+
+    // (A ∧ (B ∨ C)) ⇔ ((A ∧ B) ∨ (A ∧ C))
+    fn dual_distributivity(&mut self) {
+        self.distribute(Clause::Conjunction, Clause::Disjunction);
+    }
 
-            let mut and = match self.left().clause {
-                Clause::Conjunction => self.left.take(),
-                _ => self.right.take(),
+    fn distribute(&mut self, outer: Clause, inner: Clause) {
+        if self.clause == outer && self.children().any(|node| node.clause == inner) {
+            self.clause = inner;
+
+            let mut split = match self.left().clause == inner {
+                true => self.left.take(),
+                false => self.right.take(),
             }
             .unwrap();
             let other = match self.left {
@@ -151,25 +386,24 @@ impl Node {
             }
             .unwrap();
             let expr = Box::new(Node::new(
-                Clause::Disjunction,
+                outer,
                 Some(other.clone()),
-                Some(and.left.take().unwrap()),
+                Some(split.left.take().unwrap()),
             ));
 
-            and.clause = Clause::Disjunction;
-            and.left = Some(other);
+            split.clause = outer;
+            split.left = Some(other);
 
             self.left = Some(expr);
-            self.right = Some(and);
+            self.right = Some(split);
         }
     }
 
-    // Move con- and disjunctions to the end of the formula,
-    // but keep conjunctions at the top
+    // Move con- and disjunctions to the end of the formula. Only chains of
+    // the same operator are rotated, so whichever of the two is on top stays there.
     pub fn unbalance(&mut self) {
-        while self.children().count() == 2
-            && self.left().children().count() == 2
-            && self.clause >= self.left().clause
+        while matches!(self.clause, Clause::Conjunction | Clause::Disjunction)
+            && self.clause == self.left().clause
         {
             self.rotate_right();
         }
@@ -206,21 +440,71 @@ mod tests {
 
             nnf(&formula);
             cnf(&formula);
+            dnf(&formula);
+            reduced(&formula);
         }
     }
 
+    #[test]
+    fn reduce() {
+        compare_reduce("A1&", "A");
+        compare_reduce("A0&", "0");
+        compare_reduce("A0|", "A");
+        compare_reduce("A1|", "1");
+        compare_reduce("AA|", "A");
+        compare_reduce("AA!&", "0");
+        compare_reduce("A!A|", "1");
+        compare_reduce("AAB&|", "A");
+        compare_reduce("AB|A&", "A");
+        compare_reduce("AB&C&A|", "A");
+        compare_reduce("AB&BA&|", "AB&");
+        compare_reduce("AB1&|0|", "AB|");
+        compare_reduce("AB!B|&", "A");
+        compare_reduce("0!", "1");
+        compare_reduce("A!!", "A");
+        compare_reduce("A1^", "A!");
+        compare_reduce("A0^", "A");
+        compare_reduce("AA^", "0");
+        compare_reduce("AA!^", "1");
+        compare_reduce("A1=", "A");
+        compare_reduce("A0=", "A!");
+        compare_reduce("AA=", "1");
+        compare_reduce("A!A=", "0");
+        compare_reduce("0A>", "1");
+        compare_reduce("1A>", "A");
+        compare_reduce("A1>", "1");
+        compare_reduce("A0>", "A!");
+        compare_reduce("AA>", "1");
+        compare_reduce("AA!>", "A!");
+        compare_reduce("10>", "0");
+        compare_reduce("AB>C^", "AB>C^");
+    }
+
+    fn compare_reduce(formula: &str, expected: &str) {
+        let mut tree: Node = formula.parse().unwrap();
+
+        tree.reduce();
+
+        println!("{formula} {tree}");
+
+        assert_eq!(tree.formula(), expected);
+    }
+
     fn nnf(formula: &str) {
         let mut tree: Node = formula.parse().unwrap();
+        let original = tree.clone();
 
         tree.print();
         tree.to_nnf();
         tree.print();
 
         assert!(tree.is_nnf());
+        assert_eq!(tree.equivalent(&original), Ok(()));
     }
 
     fn cnf(formula: &str) {
         let mut tree: Node = formula.parse().unwrap();
+        let original = tree.clone();
 
         tree.print();
         tree.to_cnf();
@@ -228,6 +512,7 @@ mod tests {
         tree.print();
 
         assert!(tree.is_cnf());
+        assert_eq!(tree.equivalent(&original), Ok(()));
 
         let formula = tree.formula();
 
@@ -236,6 +521,40 @@ mod tests {
         }
     }
 
+    fn dnf(formula: &str) {
+        let mut tree: Node = formula.parse().unwrap();
+        let original = tree.clone();
+
+        tree.to_dnf();
+        tree.unbalance();
+
+        assert!(tree.is_dnf());
+        assert_eq!(tree.equivalent(&original), Ok(()));
+
+        let formula = tree.formula();
+
+        if let Some(index) = formula.chars().position(|c| c == '|') {
+            assert!(formula.chars().skip(index).all(|c| c == '|'));
+        }
+    }
+
+    fn reduced(formula: &str) {
+        let tree: Node = formula.parse().unwrap();
+        let mut reduced = tree.clone();
+        let mut nnf = tree.clone();
+        let mut cnf = tree.clone();
+
+        reduced.reduce();
+        nnf.to_nnf_reduced();
+        cnf.to_cnf_reduced();
+
+        assert!(nnf.is_nnf());
+        assert!(cnf.is_cnf());
+        assert_eq!(reduced.equivalent(&tree), Ok(()));
+        assert_eq!(nnf.equivalent(&tree), Ok(()));
+        assert_eq!(cnf.equivalent(&tree), Ok(()));
+    }
+
     fn formula(len: usize) -> String {
         let operands: Vec<char> = ('A'..='F').collect();
         let unary_operators: Vec<char> = "!".chars().collect();
@@ -1,6 +1,11 @@
-use std::{fmt::Display, iter::repeat, str::FromStr};
+use std::{fmt::Display, str::FromStr};
 
-use super::{Clause, Node};
+use super::{
+    Clause, Node,
+    error::{ParseError, ParseErrorKind},
+    infix::Style,
+    variable::Variable,
+};
 
 impl Node {
     pub fn print(&self) {
@@ -9,7 +14,7 @@ impl Node {
 
         // do while depth > 0
         loop {
-            let spaces = repeat(" ").take((1 << depth) - 1).collect::<String>();
+            let spaces = " ".repeat((1 << depth) - 1);
 
             for node in nodes.iter() {
                 print!(
@@ -17,7 +22,7 @@ impl Node {
                     if let Some(node) = node {
                         node.clause().symbol()
                     } else {
-                        ' '
+                        " ".to_string()
                     }
                 );
             }
@@ -60,37 +65,154 @@ impl Node {
 }
 
 impl FromStr for Node {
-    type Err = ();
+    type Err = ParseError;
 
     fn from_str(formula: &str) -> Result<Self, Self::Err> {
-        let mut stack = Vec::new();
-
-        for c in formula.chars() {
-            let clause = Clause::from(c);
+        // Every subtree remembers where it starts in the formula
+        let mut stack: Vec<(usize, Node)> = Vec::new();
+        let mut chars = formula.chars().enumerate();
+
+        while let Some((position, c)) = chars.next() {
+            let clause = match c {
+                '{' => Clause::Variable(name(position, &mut chars)?),
+                _ => Clause::from(c).ok_or(ParseError::new(
+                    ParseErrorKind::UnknownSymbol,
+                    position,
+                    Some(c),
+                ))?,
+            };
+            let mut pop = || {
+                stack.pop().ok_or(ParseError::new(
+                    ParseErrorKind::MissingOperand,
+                    position,
+                    Some(c),
+                ))
+            };
 
-            let node = match c {
-                'A'..='Z' | '0' | '1' => Node::new(clause, None, None),
-                '!' => Node::new(clause, Some(Box::new(stack.pop().ok_or(())?)), None),
-                '&' | '|' | '^' | '>' | '=' => {
-                    let right = Some(Box::new(stack.pop().ok_or(())?));
-                    let left = Some(Box::new(stack.pop().ok_or(())?));
+            let node = match clause {
+                Clause::Value(_) | Clause::Variable(_) => (position, Node::new(clause, None, None)),
+                Clause::Negation => {
+                    let (start, left) = pop()?;
 
-                    Node::new(clause, left, right)
+                    (start, Node::new(clause, Some(Box::new(left)), None))
+                }
+                _ => {
+                    let (_, right) = pop()?;
+                    let (start, left) = pop()?;
+
+                    (
+                        start,
+                        Node::new(clause, Some(Box::new(left)), Some(Box::new(right))),
+                    )
                 }
-                _ => return Err(()),
             };
 
             stack.push(node);
         }
 
-        let root = stack.pop().ok_or(())?;
+        match stack.len() {
+            0 => Err(ParseError::new(ParseErrorKind::EmptyInput, 0, None)),
+            1 => Ok(stack.pop().unwrap().1),
+            _ => {
+                let position = stack[1].0;
+
+                Err(ParseError::new(
+                    ParseErrorKind::TrailingOperands,
+                    position,
+                    formula.chars().nth(position),
+                ))
+            }
+        }
+    }
+}
 
-        Ok(root)
+// A multi-character variable, after its opening brace
+fn name(
+    start: usize,
+    chars: &mut impl Iterator<Item = (usize, char)>,
+) -> Result<Variable, ParseError> {
+    let mut name = String::new();
+
+    for (position, c) in chars {
+        match c {
+            '}' if Variable::is_identifier(&name) => return Ok(Variable::new(&name)),
+            _ if Variable::is_identifier(&format!("{name}{c}")) => name.push(c),
+            _ => {
+                return Err(ParseError::new(
+                    ParseErrorKind::UnknownSymbol,
+                    position,
+                    Some(c),
+                ));
+            }
+        }
     }
+
+    Err(ParseError::new(
+        ParseErrorKind::UnbalancedParenthesis,
+        start,
+        Some('{'),
+    ))
 }
 
 impl Display for Node {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        write!(f, "{}", self.formula())
+        match f.alternate() {
+            true => write!(f, "{}", self.infix(Style::Unicode)),
+            false => write!(f, "{}", self.formula()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::node::{error::*, *};
+
+    #[test]
+    fn valid() {
+        for formula in [
+            "A",
+            "1",
+            "AB&",
+            "AB&!C|",
+            "10=",
+            "AB&C&D&",
+            "{x_12}{carry3}&A|",
+        ] {
+            let node: Node = formula.parse().unwrap();
+
+            assert_eq!(node.formula(), formula);
+        }
+    }
+
+    #[test]
+    fn invalid() {
+        compare("", ParseErrorKind::EmptyInput, 0, None);
+        compare("AB#&", ParseErrorKind::UnknownSymbol, 2, Some('#'));
+        compare("Ab&", ParseErrorKind::UnknownSymbol, 1, Some('b'));
+        compare("!", ParseErrorKind::MissingOperand, 0, Some('!'));
+        compare("A&", ParseErrorKind::MissingOperand, 1, Some('&'));
+        compare("AB", ParseErrorKind::TrailingOperands, 1, Some('B'));
+        compare("AB&C", ParseErrorKind::TrailingOperands, 3, Some('C'));
+        compare("AB&CD|", ParseErrorKind::TrailingOperands, 3, Some('C'));
+        compare("A{x_1&", ParseErrorKind::UnknownSymbol, 5, Some('&'));
+        compare("A{}&", ParseErrorKind::UnknownSymbol, 2, Some('}'));
+        compare("A{1x}&", ParseErrorKind::UnknownSymbol, 2, Some('1'));
+        compare("A{xy", ParseErrorKind::UnbalancedParenthesis, 1, Some('{'));
+    }
+
+    #[test]
+    fn display() {
+        let error = "AB#&".parse::<Node>().unwrap_err();
+
+        assert_eq!(error.to_string(), "unknown symbol '#' at position 2");
+        assert_eq!(error.highlight("AB#&"), "AB#&\n  ^");
+    }
+
+    fn compare(formula: &str, kind: ParseErrorKind, position: usize, symbol: Option<char>) {
+        let error = formula.parse::<Node>().unwrap_err();
+
+        println!("{formula}: {error}");
+
+        assert_eq!(error, ParseError::new(kind, position, symbol));
     }
 }
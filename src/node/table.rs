@@ -1,79 +1,108 @@
-use super::Node;
+use super::{Clause, Node, assignment::Assignment, variable::Variable};
 use itertools::Itertools;
-use std::iter::once;
 
 impl Node {
     pub fn truth_table(&self) -> String {
-        let mut table = Vec::new();
-        let variables: String = self.variables().chars().rev().collect();
-        let columns = "|".repeat(variables.len() + 2);
-
-        table.push(format!(
-            "{}",
-            Itertools::intersperse(
-                columns
-                    .chars()
-                    .interleave(variables.chars().rev().chain(once('='))),
-                ' '
-            )
-            .collect::<String>()
-        ));
+        let variables = self.variables();
+        let header = variables.iter().map(|v| v.name()).chain(["="]).collect();
+        let btoc = |b: bool| (b as u8).to_string();
+        let rows = (0..1 << variables.len()).map(|i| {
+            let assignment = Assignment::from_bits(&variables, i);
 
-        table.push(format!(
-            "{}",
-            Itertools::intersperse(Itertools::intersperse(columns.chars(), '-'), '-')
-                .collect::<String>()
-        ));
+            assignment
+                .iter()
+                .map(|(_, b)| btoc(b))
+                .chain([btoc(self.evaluate_under(&assignment).unwrap())])
+                .collect()
+        });
 
-        let end = 1 << variables.len();
+        layout(header, rows.collect())
+    }
 
-        for i in 0..end {
-            let mapping = |c| match i & 1 << variables.chars().position(|d| d == c).unwrap() {
-                0 => false,
-                _ => true,
-            };
-            let btoc = |b: bool| (b as u8 + '0' as u8) as char;
+    // Sorted by name, without duplicates
+    pub fn variables(&self) -> Vec<Variable> {
+        self.occurrences().into_iter().unique().sorted().collect()
+    }
 
-            table.push(format!(
-                "{} {} |",
-                variables
-                    .chars()
-                    .rev()
-                    .fold(String::from("|"), |acc, c| format!(
-                        "{acc} {} |",
-                        btoc(mapping(c))
-                    )),
-                btoc(self.evaluate_with(mapping))
-            ));
+    // Every variable in the order it appears in the formula
+    pub fn occurrences(&self) -> Vec<Variable> {
+        match self.clause {
+            Clause::Variable(v) => vec![v],
+            _ => self.children().flat_map(Node::occurrences).collect(),
         }
-
-        table.join("\n")
     }
+}
 
-    pub fn sat(&self) -> bool {
-        let variables = self.variables();
-        let end = 1 << variables.len();
+// Each column as wide as its widest cell, cells centered
+pub fn layout(header: Vec<&str>, rows: Vec<Vec<String>>) -> String {
+    let widths: Vec<usize> = (0..header.len())
+        .map(|i| {
+            rows.iter()
+                .map(|row| row[i].len())
+                .fold(header[i].len(), usize::max)
+        })
+        .collect();
+    let row = |cells: Vec<String>| {
+        cells
+            .iter()
+            .zip(&widths)
+            .fold(String::from("|"), |acc, (cell, &width)| {
+                format!("{acc} {cell:^width$} |")
+            })
+    };
+    let mut table = vec![
+        row(header.iter().map(|name| name.to_string()).collect()),
+        widths.iter().fold(String::from("|"), |acc, &width| {
+            format!("{acc}{}|", "-".repeat(width + 2))
+        }),
+    ];
 
-        for i in 0..end {
-            let mapping = |c| match i & 1 << variables.chars().position(|d| d == c).unwrap() {
-                0 => false,
-                _ => true,
-            };
+    table.extend(rows.into_iter().map(row));
 
-            if self.evaluate_with(mapping) {
-                return true;
-            }
-        }
+    table.join("\n")
+}
 
-        false
+#[cfg(test)]
+mod tests {
+    use crate::node::{variable::Variable, *};
+
+    #[test]
+    fn table() {
+        let node: Node = "AB&C|".parse().unwrap();
+
+        assert_eq!(
+            node.truth_table(),
+            "\
+| A | B | C | = |
+|---|---|---|---|
+| 0 | 0 | 0 | 0 |
+| 0 | 0 | 1 | 1 |
+| 0 | 1 | 0 | 0 |
+| 0 | 1 | 1 | 1 |
+| 1 | 0 | 0 | 0 |
+| 1 | 0 | 1 | 1 |
+| 1 | 1 | 0 | 1 |
+| 1 | 1 | 1 | 1 |"
+        );
     }
 
-    pub fn variables(&self) -> String {
-        self.formula()
-            .chars()
-            .filter(char::is_ascii_uppercase)
-            .unique()
-            .sorted()
-            .collect()
+    #[test]
+    fn names() {
+        let node = Node::from_infix("carry3 & !x_12").unwrap();
+
+        assert_eq!(
+            node.variables(),
+            vec![Variable::new("carry3"), Variable::new("x_12")]
+        );
+        assert_eq!(
+            node.truth_table(),
+            "\
+| carry3 | x_12 | = |
+|--------|------|---|
+|   0    |  0   | 0 |
+|   0    |  1   | 0 |
+|   1    |  0   | 1 |
+|   1    |  1   | 0 |"
+        );
     }
 }
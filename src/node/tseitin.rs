@@ -0,0 +1,212 @@
+use super::{Clause, Node, assignment::Assignment, variable::Variable};
+use crate::solver::{Cnf, Literal};
+
+// An equisatisfiable clause database, linear in the size of the formula.
+// Variable i is the i-th of `variables`, every variable after those
+// names one of the formula's subformulas.
+pub struct Tseitin<'a> {
+    cnf: Cnf,
+    variables: Vec<Variable>,
+    auxiliary: Vec<&'a Node>,
+}
+
+// Which directions of x ⇔ φ the encoding needs (Plaisted–Greenbaum)
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum Polarity {
+    Positive,
+    Negative,
+    Both,
+}
+
+impl Polarity {
+    fn flip(self) -> Self {
+        match self {
+            Self::Positive => Self::Negative,
+            Self::Negative => Self::Positive,
+            Self::Both => Self::Both,
+        }
+    }
+
+    // x ⇒ φ
+    fn positive(self) -> bool {
+        self != Self::Negative
+    }
+
+    // φ ⇒ x
+    fn negative(self) -> bool {
+        self != Self::Positive
+    }
+}
+
+impl Node {
+    pub fn tseitin(&self) -> Tseitin<'_> {
+        let variables = self.variables();
+        let mut tseitin = Tseitin {
+            cnf: Cnf::new(variables.len()),
+            variables,
+            auxiliary: Vec::new(),
+        };
+
+        let root = tseitin.encode(self, Polarity::Positive);
+
+        tseitin.cnf.add_clause(vec![root]);
+        tseitin
+    }
+}
+
+impl<'a> Tseitin<'a> {
+    pub fn cnf(&self) -> &Cnf {
+        &self.cnf
+    }
+
+    pub fn variables(&self) -> &[Variable] {
+        &self.variables
+    }
+
+    // The subformula an auxiliary variable stands for
+    pub fn auxiliary(&self, index: usize) -> Option<&'a Node> {
+        let index = index.checked_sub(self.variables.len())?;
+
+        self.auxiliary.get(index).copied()
+    }
+
+    // The formula's own variables out of a model of the clauses
+    pub fn assignment(&self, model: &[bool]) -> Assignment {
+        self.variables
+            .iter()
+            .copied()
+            .zip(model.iter().copied())
+            .collect()
+    }
+
+    pub fn solve(&self) -> Option<Assignment> {
+        Some(self.assignment(&self.cnf.solve()?))
+    }
+
+    fn fresh(&mut self, node: &'a Node) -> Literal {
+        self.auxiliary.push(node);
+
+        Literal::new(self.cnf.fresh(), true)
+    }
+
+    fn encode(&mut self, node: &'a Node, polarity: Polarity) -> Literal {
+        let (a, b) = match node.clause {
+            Clause::Variable(v) => {
+                let index = self.variables.binary_search(&v).unwrap();
+
+                return Literal::new(index, true);
+            }
+            Clause::Value(b) => {
+                let x = self.fresh(node);
+
+                self.cnf.add_clause(vec![if b { x } else { !x }]);
+
+                return x;
+            }
+            Clause::Negation => return !self.encode(node.left(), polarity.flip()),
+            Clause::Conjunction | Clause::Disjunction => (
+                self.encode(node.left(), polarity),
+                self.encode(node.right(), polarity),
+            ),
+            Clause::Material => (
+                self.encode(node.left(), polarity.flip()),
+                self.encode(node.right(), polarity),
+            ),
+            Clause::Exclusive | Clause::Equivalence => (
+                self.encode(node.left(), Polarity::Both),
+                self.encode(node.right(), Polarity::Both),
+            ),
+        };
+
+        let x = self.fresh(node);
+
+        // (x ⇒ φ, φ ⇒ x)
+        let (positive, negative) = match node.clause {
+            Clause::Conjunction => (vec![vec![!x, a], vec![!x, b]], vec![vec![x, !a, !b]]),
+            Clause::Disjunction => (vec![vec![!x, a, b]], vec![vec![x, !a], vec![x, !b]]),
+            Clause::Exclusive => (
+                vec![vec![!x, a, b], vec![!x, !a, !b]],
+                vec![vec![x, !a, b], vec![x, a, !b]],
+            ),
+            Clause::Material => (vec![vec![!x, !a, b]], vec![vec![x, a], vec![x, !b]]),
+            Clause::Equivalence => (
+                vec![vec![!x, !a, b], vec![!x, a, !b]],
+                vec![vec![x, a, b], vec![x, !a, !b]],
+            ),
+            _ => unreachable!(),
+        };
+
+        if polarity.positive() {
+            positive
+                .into_iter()
+                .for_each(|clause| self.cnf.add_clause(clause));
+        }
+
+        if polarity.negative() {
+            negative
+                .into_iter()
+                .for_each(|clause| self.cnf.add_clause(clause));
+        }
+
+        x
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::node::{assignment::Assignment, *};
+
+    #[test]
+    fn auxiliary() {
+        let node: Node = "AB&C|".parse().unwrap();
+        let tseitin = node.tseitin();
+
+        assert_eq!(tseitin.variables().len(), 3);
+        assert!(tseitin.auxiliary(2).is_none());
+        assert_eq!(tseitin.auxiliary(3).unwrap().formula(), "AB&");
+        assert_eq!(tseitin.auxiliary(4).unwrap().formula(), "AB&C|");
+        assert!(tseitin.auxiliary(5).is_none());
+    }
+
+    #[test]
+    fn equisatisfiable() {
+        for formula in [
+            "AB&C|", "AB^A=", "AB>B!&A&", "AA^", "AB=A!B=&", "1A!&", "0A|", "0!", "AB|!C^D>",
+        ] {
+            let node: Node = formula.parse().unwrap();
+            let variables = node.variables();
+            let brute = (0..1 << variables.len()).any(|i| {
+                node.evaluate_under(&Assignment::from_bits(&variables, i))
+                    .unwrap()
+            });
+
+            match node.tseitin().solve() {
+                Some(model) => assert!(node.evaluate_under(&model).unwrap()),
+                None => assert!(!brute),
+            }
+
+            assert_eq!(node.sat(), brute);
+        }
+    }
+
+    // Hopeless through distributivity, linear here
+    #[test]
+    fn xor_chain() {
+        let names: Vec<String> = (0..20).map(|i| format!("x{i}")).collect();
+        let node = Node::from_infix(&names.join(" ^ ")).unwrap();
+        let tseitin = node.tseitin();
+
+        assert!(tseitin.cnf().clauses().len() <= 4 * names.len());
+        assert!(node.sat());
+
+        let reversed: Vec<String> = names.into_iter().rev().collect();
+        let other = Node::from_infix(&reversed.join(" ^ ")).unwrap();
+        let differ = Node::new(
+            Clause::Exclusive,
+            Some(Box::new(node)),
+            Some(Box::new(other)),
+        );
+
+        assert!(!differ.sat());
+    }
+}
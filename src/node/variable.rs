@@ -0,0 +1,94 @@
+use std::{
+    cmp::Ordering,
+    collections::HashMap,
+    fmt::{Debug, Display},
+    sync::{LazyLock, Mutex},
+};
+
+// Names are interned once and live for the rest of the program,
+// which keeps `Variable` small and `Copy` like the rest of `Clause`
+#[derive(Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Variable(u32);
+
+#[derive(Default)]
+struct Symbols {
+    names: Vec<&'static str>,
+    ids: HashMap<&'static str, u32>,
+}
+
+static SYMBOLS: LazyLock<Mutex<Symbols>> = LazyLock::new(Default::default);
+
+impl Variable {
+    // Panics if `name` is not an identifier
+    pub fn new(name: &str) -> Self {
+        assert!(Self::is_identifier(name), "Invalid variable name");
+
+        let mut symbols = SYMBOLS.lock().unwrap();
+
+        if let Some(&id) = symbols.ids.get(name) {
+            return Self(id);
+        }
+
+        let id = symbols.names.len() as u32;
+        let name: &'static str = Box::leak(name.into());
+
+        symbols.names.push(name);
+        symbols.ids.insert(name, id);
+
+        Self(id)
+    }
+
+    pub fn name(self) -> &'static str {
+        SYMBOLS.lock().unwrap().names[self.0 as usize]
+    }
+
+    // A letter or underscore, followed by letters, digits and underscores
+    pub fn is_identifier(name: &str) -> bool {
+        let mut chars = name.chars();
+
+        chars
+            .next()
+            .is_some_and(|c| c.is_ascii_alphabetic() || c == '_')
+            && chars.all(|c| c.is_ascii_alphanumeric() || c == '_')
+    }
+
+    // Single uppercase letters as is, anything else between braces
+    pub fn rpn(self) -> String {
+        let name = self.name();
+
+        match name.len() == 1 && name.chars().all(|c| c.is_ascii_uppercase()) {
+            true => name.to_string(),
+            false => format!("{{{name}}}"),
+        }
+    }
+}
+
+impl Ord for Variable {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.name().cmp(other.name())
+    }
+}
+
+impl PartialOrd for Variable {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl From<char> for Variable {
+    fn from(c: char) -> Self {
+        Self::new(c.encode_utf8(&mut [0; 4]))
+    }
+}
+
+impl Display for Variable {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.name())
+    }
+}
+
+impl Debug for Variable {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.name())
+    }
+}
@@ -1,37 +1,298 @@
-use crate::node::*;
-use itertools::Itertools;
+use std::collections::{BTreeSet, HashMap};
 
-pub fn powerset(set: Vec<i32>) -> Vec<Vec<i32>> {
-    let mut power = vec![Vec::new(), set.clone()];
+use crate::{
+    error::Error,
+    math,
+    node::{variable::Variable, *},
+};
+use bitset::Universe;
+use identity::{Counterexample, Statement};
+use venn::Venn;
 
-    for elem in set.clone() {
-        for s in powerset(set.clone().into_iter().filter(|&e| e != elem).collect()) {
-            power.push(s);
+pub mod bitset;
+pub mod identity;
+pub mod venn;
+pub mod zdd;
+
+// Subsets by size, then lexicographically. Each keeps its elements in the order
+// of `set`, where only the first of equal elements counts.
+pub fn powerset<T: Ord + Clone>(set: Vec<T>) -> Vec<Vec<T>> {
+    powerset_iter(set).collect()
+}
+
+// `powerset` one subset at a time
+pub fn powerset_iter<T: Ord + Clone>(set: Vec<T>) -> impl Iterator<Item = Vec<T>> {
+    let elements = distinct(set);
+
+    (0..=elements.len()).flat_map(move |k| combinations(elements.clone(), k))
+}
+
+// The subsets of size k, in the order of `powerset`
+pub fn combinations<T: Ord + Clone>(set: Vec<T>, k: usize) -> Combinations<T> {
+    let mut combinations = Combinations {
+        elements: distinct(set),
+        positions: Vec::with_capacity(k),
+        k,
+        done: false,
+    };
+
+    match k > combinations.elements.len() {
+        true => combinations.done = true,
+        false => combinations.fill(0),
+    }
+
+    combinations
+}
+
+// Every subset, each one element away from the one before: subset i holds the
+// elements at the set bits of the i-th gray code, the first element the lowest
+// bit. Gray codes are 32 bits wide, so at most 32 distinct elements fit.
+pub fn gray_powerset<T: Ord + Clone>(set: Vec<T>) -> impl Iterator<Item = Vec<T>> {
+    try_gray_powerset(set).unwrap()
+}
+
+pub fn try_gray_powerset<T: Ord + Clone>(
+    set: Vec<T>,
+) -> Result<impl Iterator<Item = Vec<T>>, Error> {
+    let elements = distinct(set);
+
+    if elements.len() > 32 {
+        return Err(Error::TooManyElements(elements.len()));
+    }
+
+    Ok((0..1u64 << elements.len()).map(move |i| {
+        let code = math::gray_code(i as u32);
+
+        elements
+            .iter()
+            .enumerate()
+            .filter(|&(bit, _)| code >> bit & 1 == 1)
+            .map(|(_, e)| e.clone())
+            .collect()
+    }))
+}
+
+// The first of each element, in order
+fn distinct<T: Ord + Clone>(set: Vec<T>) -> Vec<T> {
+    let mut seen = BTreeSet::new();
+
+    set.into_iter().filter(|e| seen.insert(e.clone())).collect()
+}
+
+pub struct Combinations<T> {
+    elements: Vec<T>,
+    // Where the elements of the next subset are, increasing
+    positions: Vec<usize>,
+    k: usize,
+    done: bool,
+}
+
+impl<T: Ord + Clone> Combinations<T> {
+    // The position of the smallest element from `from` on, above `above`, that
+    // leaves room for `rest` more after it
+    fn smallest(&self, from: usize, rest: usize, above: Option<&T>) -> Option<usize> {
+        (from..self.elements.len() - rest)
+            .filter(|&p| above.is_none_or(|above| &self.elements[p] > above))
+            .min_by_key(|&p| &self.elements[p])
+    }
+
+    // Complete the positions from `level` on with the smallest choices
+    fn fill(&mut self, level: usize) {
+        self.positions.truncate(level);
+
+        for j in level..self.k {
+            let from = self.positions.last().map_or(0, |p| p + 1);
+            let p = self.smallest(from, self.k - j - 1, None).unwrap();
+
+            self.positions.push(p);
+        }
+    }
+}
+
+impl<T: Ord + Clone> Iterator for Combinations<T> {
+    type Item = Vec<T>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.done {
+            return None;
+        }
+
+        let subset = self
+            .positions
+            .iter()
+            .map(|&p| self.elements[p].clone())
+            .collect();
+
+        // Raise the last element that has a larger choice, refill the ones after it
+        for i in (0..self.k).rev() {
+            let from = if i == 0 { 0 } else { self.positions[i - 1] + 1 };
+            let current = &self.elements[self.positions[i]];
+
+            if let Some(p) = self.smallest(from, self.k - i - 1, Some(current)) {
+                self.positions[i] = p;
+                self.fill(i + 1);
+                return Some(subset);
+            }
         }
+
+        self.done = true;
+        Some(subset)
+    }
+}
+
+// Sorted, without duplicates. The k-th set is bound to the k-th variable in
+// alphabetical order, as in `Node::variables`, and complements are taken within
+// the union of the sets.
+pub fn eval_set<T: Ord + Clone>(formula: &str, sets: Vec<Vec<T>>) -> Vec<T> {
+    try_eval_set(formula, sets).unwrap()
+}
+
+pub fn try_eval_set<T: Ord + Clone>(formula: &str, sets: Vec<Vec<T>>) -> Result<Vec<T>, Error> {
+    let universe = Universe::new(sets.iter().flatten().cloned());
+
+    try_eval_set_in(formula, sets, &universe)
+}
+
+// As `eval_set`, complements taken within `universe`
+pub fn eval_set_in<T: Ord + Clone>(
+    formula: &str,
+    sets: Vec<Vec<T>>,
+    universe: &Universe<T>,
+) -> Vec<T> {
+    try_eval_set_in(formula, sets, universe).unwrap()
+}
+
+pub fn try_eval_set_in<T: Ord + Clone>(
+    formula: &str,
+    sets: Vec<Vec<T>>,
+    universe: &Universe<T>,
+) -> Result<Vec<T>, Error> {
+    let tree: Node = formula.parse()?;
+    let variables = tree.variables();
+
+    if sets.len() > variables.len() {
+        return Err(Error::ExtraSets {
+            expected: variables.len(),
+            found: sets.len(),
+        });
     }
 
-    power
-        .into_iter()
-        .unique()
-        .sorted_by(|a, b| match Ord::cmp(&a.len(), &b.len()) {
-            std::cmp::Ordering::Equal => a.cmp(b),
-            o @ _ => o,
-        })
-        .collect()
+    evaluate(&tree, &variables.into_iter().zip(sets).collect(), universe)
+}
+
+// As `eval_set`, each set bound to a variable by name. Every variable of the
+// formula needs a set, and every set a variable of the formula.
+pub fn eval_set_named<T: Ord + Clone>(formula: &str, sets: &HashMap<Variable, Vec<T>>) -> Vec<T> {
+    try_eval_set_named(formula, sets).unwrap()
+}
+
+pub fn try_eval_set_named<T: Ord + Clone>(
+    formula: &str,
+    sets: &HashMap<Variable, Vec<T>>,
+) -> Result<Vec<T>, Error> {
+    let universe = Universe::new(sets.values().flatten().cloned());
+
+    try_eval_set_named_in(formula, sets, &universe)
 }
 
-pub fn eval_set(formula: &str, sets: Vec<Vec<i32>>) -> Vec<i32> {
-    let tree: Node = formula.parse().unwrap();
-    let encompassing: Vec<i32> = sets.clone().into_iter().flatten().unique().collect();
-    let variables: String = formula.chars().filter(char::is_ascii_uppercase).collect();
+// As `eval_set_named`, complements taken within `universe`
+pub fn eval_set_named_in<T: Ord + Clone>(
+    formula: &str,
+    sets: &HashMap<Variable, Vec<T>>,
+    universe: &Universe<T>,
+) -> Vec<T> {
+    try_eval_set_named_in(formula, sets, universe).unwrap()
+}
+
+pub fn try_eval_set_named_in<T: Ord + Clone>(
+    formula: &str,
+    sets: &HashMap<Variable, Vec<T>>,
+    universe: &Universe<T>,
+) -> Result<Vec<T>, Error> {
+    let tree: Node = formula.parse()?;
+
+    evaluate(&tree, sets, universe)
+}
+
+// Whether `lhs` = `rhs` for every choice of sets, or sets for which it fails
+pub fn identity_holds(lhs: &str, rhs: &str) -> Result<(), Counterexample> {
+    try_identity_holds(lhs, rhs).unwrap()
+}
+
+pub fn try_identity_holds(lhs: &str, rhs: &str) -> Result<Result<(), Counterexample>, Error> {
+    Ok(Statement::equal(lhs, rhs)?.holds())
+}
+
+// Whether `lhs` ⊆ `rhs` for every choice of sets, or sets for which it fails
+pub fn inclusion_holds(lhs: &str, rhs: &str) -> Result<(), Counterexample> {
+    try_inclusion_holds(lhs, rhs).unwrap()
+}
+
+pub fn try_inclusion_holds(lhs: &str, rhs: &str) -> Result<Result<(), Counterexample>, Error> {
+    Ok(Statement::subset(lhs, rhs)?.holds())
+}
 
-    tree.evaluate_sets(&encompassing, |c| {
-        sets[variables.chars().position(|d| d == c).unwrap()].clone()
-    })
+// Which sets each element of the union of `sets` is in, and whether the formula
+// keeps it
+pub fn venn<T: Ord + Clone>(formula: &str, sets: &HashMap<Variable, Vec<T>>) -> Venn<T> {
+    try_venn(formula, sets).unwrap()
+}
+
+pub fn try_venn<T: Ord + Clone>(
+    formula: &str,
+    sets: &HashMap<Variable, Vec<T>>,
+) -> Result<Venn<T>, Error> {
+    let universe = Universe::new(sets.values().flatten().cloned());
+
+    try_venn_in(formula, sets, &universe)
+}
+
+// As `venn`, over every element of `universe`
+pub fn venn_in<T: Ord + Clone>(
+    formula: &str,
+    sets: &HashMap<Variable, Vec<T>>,
+    universe: &Universe<T>,
+) -> Venn<T> {
+    try_venn_in(formula, sets, universe).unwrap()
+}
+
+pub fn try_venn_in<T: Ord + Clone>(
+    formula: &str,
+    sets: &HashMap<Variable, Vec<T>>,
+    universe: &Universe<T>,
+) -> Result<Venn<T>, Error> {
+    Venn::new(formula.parse()?, sets, universe)
+}
+
+fn evaluate<T: Ord + Clone>(
+    tree: &Node,
+    sets: &HashMap<Variable, Vec<T>>,
+    universe: &Universe<T>,
+) -> Result<Vec<T>, Error> {
+    check_bindings(tree, sets)?;
+
+    tree.evaluate_sets(universe, |v| sets.get(&v).cloned())
+}
+
+// Every variable bound and every binding used, the first offender in
+// alphabetical order reported
+fn check_bindings<T>(tree: &Node, sets: &HashMap<Variable, Vec<T>>) -> Result<(), Error> {
+    let variables = tree.variables();
+
+    if let Some(&v) = variables.iter().find(|v| !sets.contains_key(v)) {
+        return Err(Error::MissingSet(v));
+    }
+
+    match sets.keys().filter(|v| !variables.contains(v)).min() {
+        Some(&v) => Err(Error::UnusedSet(v)),
+        None => Ok(()),
+    }
 }
 
 #[cfg(test)]
 mod tests {
+    use itertools::Itertools;
+
     use super::*;
 
     #[test]
@@ -45,6 +306,229 @@ mod tests {
         compare("A!", vec![vec![0, 1, 2]], vec![]);
     }
 
+    #[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord)]
+    enum Permission {
+        Read,
+        Write,
+        Admin,
+    }
+
+    #[test]
+    fn generic() {
+        let users = |names: &[&str]| names.iter().map(|name| name.to_string()).collect();
+
+        assert_eq!(
+            eval_set(
+                "AB&C!|",
+                vec![
+                    users(&["bob", "alice", "carol"]),
+                    users(&["carol", "alice"]),
+                    users(&["dave", "alice", "bob"]),
+                ]
+            ),
+            ["alice", "carol"]
+        );
+        assert_eq!(
+            eval_set(
+                "AB^",
+                vec![
+                    vec![Permission::Admin, Permission::Read],
+                    vec![Permission::Write, Permission::Read],
+                ]
+            ),
+            [Permission::Write, Permission::Admin]
+        );
+        assert_eq!(
+            super::powerset(vec!["b", "a"]),
+            [vec![], vec!["a"], vec!["b"], vec!["b", "a"]]
+        );
+        assert_eq!(
+            super::combinations(
+                vec![Permission::Admin, Permission::Read, Permission::Write],
+                2
+            )
+            .next(),
+            Some(vec![Permission::Read, Permission::Write])
+        );
+    }
+
+    #[test]
+    fn powerset() {
+        assert_eq!(
+            super::powerset(vec![1, 2, 3]),
+            [
+                vec![],
+                vec![1],
+                vec![2],
+                vec![3],
+                vec![1, 2],
+                vec![1, 3],
+                vec![2, 3],
+                vec![1, 2, 3],
+            ]
+        );
+
+        // The subsets keep the order of the elements, and are sorted as such
+        assert_eq!(
+            super::powerset(vec![3, 1, 2, 1]),
+            [
+                vec![],
+                vec![1],
+                vec![2],
+                vec![3],
+                vec![1, 2],
+                vec![3, 1],
+                vec![3, 2],
+                vec![3, 1, 2],
+            ]
+        );
+        assert_eq!(super::powerset(Vec::<i32>::new()), [Vec::<i32>::new()]);
+
+        // Streams where collecting 2^40 subsets could not
+        let mut subsets = powerset_iter((0..40).collect());
+
+        assert_eq!(subsets.nth(41), Some(vec![0, 1]));
+    }
+
+    #[test]
+    fn combinations() {
+        assert_eq!(
+            super::combinations(vec![4, 3, 2, 1], 2).collect::<Vec<_>>(),
+            [[2, 1], [3, 1], [3, 2], [4, 1], [4, 2], [4, 3]]
+        );
+        assert_eq!(super::combinations(vec![1, 2], 0).count(), 1);
+        assert_eq!(super::combinations(vec![1, 2], 3).count(), 0);
+        assert_eq!(super::combinations((0..20).collect(), 10).count(), 184756);
+    }
+
+    #[test]
+    fn gray() {
+        let subsets: Vec<Vec<i32>> = gray_powerset(vec![1, 2, 3]).collect();
+
+        assert_eq!(
+            subsets,
+            [
+                vec![],
+                vec![1],
+                vec![1, 2],
+                vec![2],
+                vec![2, 3],
+                vec![1, 2, 3],
+                vec![1, 3],
+                vec![3],
+            ]
+        );
+
+        for pair in subsets.windows(2) {
+            let (a, b) = (&pair[0], &pair[1]);
+            let changed = a.iter().filter(|e| !b.contains(e)).count()
+                + b.iter().filter(|e| !a.contains(e)).count();
+
+            assert_eq!(changed, 1);
+        }
+
+        assert_eq!(
+            subsets.into_iter().sorted().collect::<Vec<_>>(),
+            super::powerset(vec![1, 2, 3])
+                .into_iter()
+                .sorted()
+                .collect::<Vec<_>>()
+        );
+
+        assert_eq!(
+            gray_powerset(vec![2, 1]).collect::<Vec<_>>(),
+            [vec![], vec![2], vec![2, 1], vec![1]]
+        );
+        assert_eq!(gray_powerset((0..32).collect()).nth(3), Some(vec![1]));
+        assert!(matches!(
+            try_gray_powerset((0..33).collect()),
+            Err(Error::TooManyElements(33))
+        ));
+    }
+
+    #[test]
+    fn universe() {
+        let universe = Universe::new(0..10);
+
+        assert_eq!(
+            eval_set_in("A!", vec![vec![0, 1, 2]], &universe),
+            [3, 4, 5, 6, 7, 8, 9]
+        );
+        assert_eq!(
+            eval_set_in("AB>", vec![vec![0, 1], vec![1, 2]], &universe),
+            [1, 2, 3, 4, 5, 6, 7, 8, 9]
+        );
+        assert_eq!(
+            eval_set_in("AB=", vec![vec![0, 1], vec![1, 2]], &universe),
+            [1, 3, 4, 5, 6, 7, 8, 9]
+        );
+        assert_eq!(
+            try_eval_set_in("AB|", vec![vec![0], vec![10]], &universe),
+            Err(Error::OutsideUniverse(Variable::from('B')))
+        );
+
+        // Linear in the size of the universe
+        let universe = Universe::new(0..1_000_000);
+        let evens: Vec<i32> = (0..1_000_000).step_by(2).collect();
+        let thirds: Vec<i32> = (0..1_000_000).step_by(3).collect();
+        let set = eval_set_in("AB|!", vec![evens, thirds], &universe);
+
+        assert_eq!(set.len(), 333_333);
+        assert_eq!(set[..3], [1, 5, 7]);
+    }
+
+    #[test]
+    fn errors() {
+        assert!(matches!(
+            try_eval_set("A&", vec![vec![0]]),
+            Err(Error::Parse(_))
+        ));
+        assert_eq!(
+            try_eval_set("AB&", vec![vec![0]]),
+            Err(Error::MissingSet(Variable::from('B')))
+        );
+        assert_eq!(
+            try_eval_set("A1&", vec![vec![0]]),
+            Err(Error::ConstantInSet(true))
+        );
+    }
+
+    #[test]
+    fn named() {
+        let sets = HashMap::from([
+            (Variable::new("admins"), vec![0, 1]),
+            (Variable::new("users"), vec![1, 2, 3]),
+        ]);
+
+        assert_eq!(eval_set_named("{users}{admins}!&", &sets), [2, 3]);
+        assert_eq!(
+            eval_set_named_in("{admins}{users}|!", &sets, &Universe::new(0..6)),
+            [4, 5]
+        );
+        assert_eq!(
+            try_eval_set_named("{users}{guests}&", &sets),
+            Err(Error::MissingSet(Variable::new("guests")))
+        );
+        assert_eq!(
+            try_eval_set_named("{users}", &sets),
+            Err(Error::UnusedSet(Variable::new("admins")))
+        );
+    }
+
+    #[test]
+    fn positional() {
+        // Bound alphabetically, not by first appearance
+        compare("BA!&", vec![vec![0, 1], vec![1, 2]], vec![2]);
+        compare("BAB|&", vec![vec![0, 1], vec![1, 2]], vec![1, 2]);
+        assert_eq!(
+            try_eval_set("A", vec![vec![0], vec![1]]),
+            Err(Error::ExtraSets {
+                expected: 1,
+                found: 2
+            })
+        );
+    }
+
     fn compare(formula: &str, sets: Vec<Vec<i32>>, res: Vec<i32>) {
         assert_eq!(res, eval_set(formula, sets));
     }
@@ -0,0 +1,204 @@
+use std::ops::{BitAnd, BitOr, BitXor, Not};
+
+// Membership over the indices 0..width, 64 to a word. Bits past `width` stay clear.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct Bitset {
+    words: Vec<u64>,
+    width: usize,
+}
+
+impl Bitset {
+    pub fn new(width: usize) -> Self {
+        Self {
+            words: vec![0; width.div_ceil(64)],
+            width,
+        }
+    }
+
+    // The number of indices, set or not; `count` is the number set
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    // No index set
+    pub fn is_empty(&self) -> bool {
+        self.words.iter().all(|&word| word == 0)
+    }
+
+    pub fn insert(&mut self, index: usize) {
+        assert!(index < self.width, "index {index} out of {}", self.width);
+
+        self.words[index / 64] |= 1 << (index % 64);
+    }
+
+    pub fn contains(&self, index: usize) -> bool {
+        index < self.width && self.words[index / 64] >> (index % 64) & 1 == 1
+    }
+
+    pub fn count(&self) -> usize {
+        self.words
+            .iter()
+            .map(|word| word.count_ones() as usize)
+            .sum()
+    }
+
+    // The set indices, ascending
+    pub fn iter(&self) -> impl Iterator<Item = usize> + '_ {
+        self.words.iter().enumerate().flat_map(|(i, &word)| {
+            (0..64)
+                .filter(move |bit| word >> bit & 1 == 1)
+                .map(move |bit| i * 64 + bit)
+        })
+    }
+
+    fn zip(&self, other: &Self, f: impl Fn(u64, u64) -> u64) -> Self {
+        assert_eq!(self.width, other.width, "bitsets of different widths");
+
+        Self {
+            words: self
+                .words
+                .iter()
+                .zip(&other.words)
+                .map(|(&a, &b)| f(a, b))
+                .collect(),
+            width: self.width,
+        }
+    }
+
+    // Clear the bits past `width`, which complementing would otherwise set
+    fn trim(mut self) -> Self {
+        if let Some(last) = self.words.last_mut()
+            && !self.width.is_multiple_of(64)
+        {
+            *last &= (1 << (self.width % 64)) - 1;
+        }
+
+        self
+    }
+}
+
+impl BitAnd for &Bitset {
+    type Output = Bitset;
+
+    fn bitand(self, other: Self) -> Bitset {
+        self.zip(other, |a, b| a & b)
+    }
+}
+
+impl BitOr for &Bitset {
+    type Output = Bitset;
+
+    fn bitor(self, other: Self) -> Bitset {
+        self.zip(other, |a, b| a | b)
+    }
+}
+
+impl BitXor for &Bitset {
+    type Output = Bitset;
+
+    fn bitxor(self, other: Self) -> Bitset {
+        self.zip(other, |a, b| a ^ b)
+    }
+}
+
+impl Not for &Bitset {
+    type Output = Bitset;
+
+    fn not(self) -> Bitset {
+        Bitset {
+            words: self.words.iter().map(|word| !word).collect(),
+            width: self.width,
+        }
+        .trim()
+    }
+}
+
+// The domain complements are taken in, each element numbered by its rank
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Universe<T> {
+    elements: Vec<T>,
+}
+
+impl<T: Ord + Clone> Universe<T> {
+    pub fn new(elements: impl IntoIterator<Item = T>) -> Self {
+        let mut elements: Vec<T> = elements.into_iter().collect();
+
+        elements.sort();
+        elements.dedup();
+
+        Self { elements }
+    }
+
+    // Sorted, without duplicates
+    pub fn elements(&self) -> &[T] {
+        &self.elements
+    }
+
+    // The number of elements, the width of its bitsets
+    pub fn size(&self) -> usize {
+        self.elements.len()
+    }
+
+    pub fn index(&self, element: &T) -> Option<usize> {
+        self.elements.binary_search(element).ok()
+    }
+
+    // None if the set reaches outside the universe
+    pub fn bitset(&self, set: &[T]) -> Option<Bitset> {
+        let mut bits = Bitset::new(self.size());
+
+        for element in set {
+            bits.insert(self.index(element)?);
+        }
+
+        Some(bits)
+    }
+
+    pub fn set(&self, bits: &Bitset) -> Vec<T> {
+        bits.iter().map(|i| self.elements[i].clone()).collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn operators() {
+        let mut a = Bitset::new(130);
+        let mut b = Bitset::new(130);
+
+        for i in [0, 63, 64, 129] {
+            a.insert(i);
+        }
+
+        for i in [63, 100, 129] {
+            b.insert(i);
+        }
+
+        assert_eq!((&a & &b).iter().collect::<Vec<_>>(), [63, 129]);
+        assert_eq!((&a | &b).iter().collect::<Vec<_>>(), [0, 63, 64, 100, 129]);
+        assert_eq!((&a ^ &b).iter().collect::<Vec<_>>(), [0, 64, 100]);
+        assert_eq!((!&a).count(), 130 - 4);
+        assert!(!(!&a).contains(129));
+        assert!(!(!&a).contains(130));
+        assert_eq!((!&a).width(), 130);
+        assert!((&a & &!&a).is_empty());
+        assert!(!a.is_empty());
+        assert_eq!(!&!&a, a);
+    }
+
+    #[test]
+    fn universe() {
+        let universe = Universe::new(["write", "read", "admin", "read"]);
+
+        assert_eq!(universe.elements(), ["admin", "read", "write"]);
+        assert_eq!(universe.index(&"read"), Some(1));
+        assert_eq!(universe.size(), 3);
+
+        let bits = universe.bitset(&["write", "admin"]).unwrap();
+
+        assert_eq!(universe.set(&!&bits), ["read"]);
+        assert_eq!(universe.bitset(&["root"]), None);
+    }
+}
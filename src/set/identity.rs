@@ -0,0 +1,222 @@
+use std::{collections::BTreeMap, fmt::Display};
+
+use crate::{
+    error::Error,
+    node::{Node, assignment::Assignment, clause::Clause, variable::Variable},
+};
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Relation {
+    Equal,
+    Subset,
+}
+
+// A relation between two set formulas, read as in `eval_set`, so without
+// constants. It holds for every choice of sets exactly when, element by element,
+// the matching propositional formula is a tautology.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Statement {
+    lhs: Node,
+    relation: Relation,
+    rhs: Node,
+}
+
+impl Statement {
+    pub fn new(lhs: Node, relation: Relation, rhs: Node) -> Result<Self, Error> {
+        match lhs.constant().or(rhs.constant()) {
+            Some(b) => Err(Error::ConstantInSet(b)),
+            None => Ok(Self { lhs, relation, rhs }),
+        }
+    }
+
+    pub fn equal(lhs: &str, rhs: &str) -> Result<Self, Error> {
+        Self::new(lhs.parse()?, Relation::Equal, rhs.parse()?)
+    }
+
+    pub fn subset(lhs: &str, rhs: &str) -> Result<Self, Error> {
+        Self::new(lhs.parse()?, Relation::Subset, rhs.parse()?)
+    }
+
+    pub fn holds(&self) -> Result<(), Counterexample> {
+        self.holds_given(&[])
+    }
+
+    // Whether every choice of sets that satisfies all of `hypotheses` satisfies
+    // this too. Each statement quantifies over the elements on its own, so a
+    // failure always shows up on a single element.
+    pub fn holds_given(&self, hypotheses: &[Statement]) -> Result<(), Counterexample> {
+        let premises: Vec<Node> = hypotheses.iter().map(Statement::formula).collect();
+        let conclusion = self.formula();
+        let Err(mut assignment) = conclusion.follows_from(&premises) else {
+            return Ok(());
+        };
+        let fails = |assignment: &Assignment| {
+            premises
+                .iter()
+                .all(|premise| premise.evaluate_under(assignment) == Ok(true))
+                && conclusion.evaluate_under(assignment) == Ok(false)
+        };
+
+        // Take the element out of every set it can leave
+        for (v, member) in assignment.clone().iter() {
+            if member {
+                assignment.set(v, false);
+
+                if !fails(&assignment) {
+                    assignment.set(v, true);
+                }
+            }
+        }
+
+        Err(Counterexample(
+            assignment
+                .iter()
+                .map(|(v, member)| (v, if member { vec![0] } else { vec![] }))
+                .collect(),
+        ))
+    }
+
+    // What the statement says about a single element
+    fn formula(&self) -> Node {
+        let operator = match self.relation {
+            Relation::Equal => Clause::Equivalence,
+            Relation::Subset => Clause::Material,
+        };
+
+        self.lhs.join(operator, &self.rhs)
+    }
+}
+
+// Sets over the universe {0}, the element in as few of them as will still break
+// the statement
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Counterexample(BTreeMap<Variable, Vec<u32>>);
+
+impl Counterexample {
+    pub fn get(&self, variable: Variable) -> Option<&[u32]> {
+        self.0.get(&variable).map(Vec::as_slice)
+    }
+
+    // Sorted by variable
+    pub fn iter(&self) -> impl Iterator<Item = (Variable, &[u32])> {
+        self.0.iter().map(|(&v, set)| (v, set.as_slice()))
+    }
+}
+
+impl Display for Counterexample {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let sets: Vec<String> = self
+            .iter()
+            .map(|(v, set)| {
+                let elements: Vec<String> = set.iter().map(u32::to_string).collect();
+
+                format!("{v}={{{}}}", elements.join(","))
+            })
+            .collect();
+
+        write!(f, "{}", sets.join(" "))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use crate::set::{identity::*, *};
+
+    #[test]
+    fn identities() {
+        // Distributivity, De Morgan, absorption
+        assert_eq!(identity_holds("ABC|&", "AB&AC&|"), Ok(()));
+        assert_eq!(identity_holds("AB|!", "A!B!&"), Ok(()));
+        assert_eq!(identity_holds("AAB&|", "A"), Ok(()));
+        // Both sides empty, which a constant cannot say
+        assert_eq!(identity_holds("AA!&", "BB!&"), Ok(()));
+        assert_eq!(inclusion_holds("AB&", "A"), Ok(()));
+        assert_eq!(inclusion_holds("A", "AB^C|A|"), Ok(()));
+    }
+
+    #[test]
+    fn counterexamples() {
+        let counterexample = identity_holds("ABC&|", "AB|C&").unwrap_err();
+
+        assert_eq!(counterexample.to_string(), "A={0} B={} C={}");
+        check("ABC&|", "AB|C&", &counterexample);
+
+        let counterexample = inclusion_holds("AB|", "A").unwrap_err();
+
+        assert_eq!(counterexample.to_string(), "A={} B={0}");
+
+        // Only the sets the statement needs hold the element
+        let counterexample = identity_holds("AB|C|D|", "ABCD&&&").unwrap_err();
+
+        assert_eq!(
+            counterexample
+                .iter()
+                .filter(|(_, set)| !set.is_empty())
+                .count(),
+            1
+        );
+        check("AB|C|D|", "ABCD&&&", &counterexample);
+    }
+
+    #[test]
+    fn hypotheses() {
+        let subset = Statement::subset("A", "B").unwrap();
+        let conclusion = Statement::equal("AB|", "B").unwrap();
+
+        assert!(conclusion.holds().is_err());
+        assert_eq!(
+            conclusion.holds_given(std::slice::from_ref(&subset)),
+            Ok(())
+        );
+
+        // Transitivity, and what goes wrong without one of its premises
+        let transitive = Statement::subset("A", "C").unwrap();
+        let chain = Statement::subset("B", "C").unwrap();
+
+        assert_eq!(transitive.holds_given(&[subset.clone(), chain]), Ok(()));
+        assert_eq!(
+            transitive.holds_given(&[subset]).unwrap_err().to_string(),
+            "A={0} B={0} C={}"
+        );
+    }
+
+    #[test]
+    fn errors() {
+        assert!(matches!(
+            try_identity_holds("A&", "A"),
+            Err(Error::Parse(_))
+        ));
+        assert!(matches!(
+            try_inclusion_holds("A", "AB"),
+            Err(Error::Parse(_))
+        ));
+        assert_eq!(
+            try_identity_holds("AA!&", "0"),
+            Err(Error::ConstantInSet(false))
+        );
+        assert_eq!(
+            try_inclusion_holds("A1&", "A"),
+            Err(Error::ConstantInSet(true))
+        );
+    }
+
+    // The counterexample, evaluated, tells the two sides apart
+    fn check(lhs: &str, rhs: &str, counterexample: &Counterexample) {
+        let universe = Universe::new([0]);
+        let sets = |formula: &str| -> HashMap<Variable, Vec<u32>> {
+            let tree: Node = formula.parse().unwrap();
+
+            tree.variables()
+                .into_iter()
+                .map(|v| (v, counterexample.get(v).unwrap().to_vec()))
+                .collect()
+        };
+
+        assert_ne!(
+            eval_set_named_in(lhs, &sets(lhs), &universe),
+            eval_set_named_in(rhs, &sets(rhs), &universe)
+        );
+    }
+}
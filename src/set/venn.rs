@@ -0,0 +1,217 @@
+use std::{
+    collections::{BTreeMap, HashMap},
+    fmt::Display,
+};
+
+use super::{bitset::Universe, check_bindings};
+use crate::{
+    error::Error,
+    node::{Node, assignment::Assignment, table, variable::Variable},
+};
+
+// Where each element of the universe falls among the sets of a formula
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Venn<T> {
+    tree: Node,
+    variables: Vec<Variable>,
+    memberships: Vec<Membership<T>>,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Membership<T> {
+    element: T,
+    sets: Vec<bool>,
+    result: bool,
+}
+
+impl<T> Membership<T> {
+    pub fn element(&self) -> &T {
+        &self.element
+    }
+
+    // One entry per variable, in the order of `Venn::variables`
+    pub fn sets(&self) -> &[bool] {
+        &self.sets
+    }
+
+    // Whether the formula's set holds the element
+    pub fn result(&self) -> bool {
+        self.result
+    }
+}
+
+impl<T: Ord + Clone> Venn<T> {
+    pub(super) fn new(
+        tree: Node,
+        sets: &HashMap<Variable, Vec<T>>,
+        universe: &Universe<T>,
+    ) -> Result<Self, Error> {
+        check_bindings(&tree, sets)?;
+
+        let variables = tree.variables();
+        let bits = |v: Variable| universe.bitset(&sets[&v]).ok_or(Error::OutsideUniverse(v));
+        let columns = variables
+            .iter()
+            .map(|&v| bits(v))
+            .collect::<Result<Vec<_>, _>>()?;
+        let result = tree.evaluate_bits(bits)?;
+        let memberships = universe
+            .elements()
+            .iter()
+            .enumerate()
+            .map(|(i, element)| Membership {
+                element: element.clone(),
+                sets: columns.iter().map(|column| column.contains(i)).collect(),
+                result: result.contains(i),
+            })
+            .collect();
+
+        Ok(Self {
+            tree,
+            variables,
+            memberships,
+        })
+    }
+
+    // Sorted by name, without duplicates
+    pub fn variables(&self) -> &[Variable] {
+        &self.variables
+    }
+
+    // In the order of the universe
+    pub fn memberships(&self) -> &[Membership<T>] {
+        &self.memberships
+    }
+
+    // The elements in exactly the sets marked in `sets`
+    pub fn region(&self, sets: &[bool]) -> Vec<T> {
+        self.memberships
+            .iter()
+            .filter(|m| m.sets == sets)
+            .map(|m| m.element.clone())
+            .collect()
+    }
+
+    // The truth table of the formula, each row listing the elements of its region
+    pub fn table(&self) -> String
+    where
+        T: Display,
+    {
+        self.try_table().unwrap()
+    }
+
+    // Rows are numbered in a u64, so at most 64 variables
+    pub fn try_table(&self) -> Result<String, Error>
+    where
+        T: Display,
+    {
+        let n = self.variables.len();
+        if n > 64 {
+            return Err(Error::TooManyVariables(n));
+        }
+        let last = u64::MAX.checked_shr(64 - n as u32).unwrap_or(0);
+        let header = self
+            .variables
+            .iter()
+            .map(|v| v.name())
+            .chain(["=", "elements"])
+            .collect();
+        // One pass over the universe, each element filed under its row
+        let mut regions: BTreeMap<u64, Vec<String>> = BTreeMap::new();
+        for m in &self.memberships {
+            let row = m.sets.iter().fold(0, |row, &b| row << 1 | b as u64);
+
+            regions.entry(row).or_default().push(m.element.to_string());
+        }
+        let btoc = |b: bool| (b as u8).to_string();
+        let rows = (0..=last).map(|i| {
+            let assignment = Assignment::from_bits(&self.variables, i);
+            let elements = regions.remove(&i).unwrap_or_default();
+
+            assignment
+                .iter()
+                .map(|(_, b)| btoc(b))
+                .chain([btoc(self.tree.evaluate_under(&assignment).unwrap())])
+                .chain([elements.join(",")])
+                .collect()
+        });
+
+        Ok(table::layout(header, rows.collect()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{error::Error, node::variable::Variable, set::*};
+
+    #[test]
+    fn regions() {
+        let sets = HashMap::from([
+            (Variable::from('A'), vec![0, 1, 2]),
+            (Variable::from('B'), vec![2, 3]),
+        ]);
+        let venn = venn_in("AB!&", &sets, &Universe::new(0..6));
+
+        assert_eq!(venn.variables(), [Variable::from('A'), Variable::from('B')]);
+        assert_eq!(venn.memberships()[2].sets(), [true, true]);
+        assert!(!venn.memberships()[2].result());
+        assert_eq!(venn.region(&[false, false]), [4, 5]);
+        assert_eq!(
+            venn.table(),
+            "\
+| A | B | = | elements |
+|---|---|---|----------|
+| 0 | 0 | 0 |   4,5    |
+| 0 | 1 | 0 |    3     |
+| 1 | 0 | 1 |   0,1    |
+| 1 | 1 | 0 |    2     |"
+        );
+        assert_eq!(
+            venn.memberships()
+                .iter()
+                .filter(|m| m.result())
+                .map(|m| *m.element())
+                .collect::<Vec<_>>(),
+            eval_set_named_in("AB!&", &sets, &Universe::new(0..6))
+        );
+
+        let sets = HashMap::from([
+            (Variable::from('A'), vec![0]),
+            (Variable::from('B'), vec![0, 1]),
+        ]);
+
+        assert_eq!(
+            venn_in("AB|", &sets, &Universe::new(0..3)).table(),
+            "\
+| A | B | = | elements |
+|---|---|---|----------|
+| 0 | 0 | 0 |    2     |
+| 0 | 1 | 1 |    1     |
+| 1 | 0 | 1 |          |
+| 1 | 1 | 1 |    0     |"
+        );
+    }
+
+    #[test]
+    fn errors() {
+        let sets = HashMap::from([(Variable::from('A'), vec![0])]);
+
+        assert_eq!(
+            try_venn_in("AB|", &sets, &Universe::new(0..2)),
+            Err(Error::MissingSet(Variable::from('B')))
+        );
+        assert_eq!(
+            try_venn_in("A", &sets, &Universe::new(1..2)),
+            Err(Error::OutsideUniverse(Variable::from('A')))
+        );
+        assert_eq!(try_venn("A", &sets).unwrap().memberships().len(), 1);
+
+        let variables: Vec<_> = (0..65).map(|i| Variable::new(&format!("x{i}"))).collect();
+        let formula: String = (1..65).map(|i| format!("{{x{i}}}|")).collect();
+        let formula = format!("{{x0}}{formula}");
+        let sets = variables.iter().map(|&v| (v, vec![0])).collect();
+        let venn = try_venn_in(&formula, &sets, &Universe::new(0..1)).unwrap();
+
+        assert_eq!(venn.try_table(), Err(Error::TooManyVariables(65)));
+    }
+}
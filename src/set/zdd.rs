@@ -0,0 +1,470 @@
+use std::collections::HashMap;
+
+// A family of sets in a `Manager`. Equal families of the same manager are the same handle.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Family(u32);
+
+impl Family {
+    // No sets at all
+    pub const EMPTY: Self = Self(0);
+    // Just the empty set
+    pub const BASE: Self = Self(1);
+
+    pub fn is_constant(self) -> bool {
+        self.0 < 2
+    }
+
+    fn index(self) -> usize {
+        self.0 as usize
+    }
+}
+
+// The sets without `element` in `low`, those with it in `high`, `element` taken out
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+struct Entry {
+    // Wider than the elements, so the terminals sort below all of them
+    element: i64,
+    low: Family,
+    high: Family,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+enum Operation {
+    Union,
+    Intersection,
+    Difference,
+    Join,
+}
+
+// Zero-suppressed decision diagrams: a node whose `high` is empty is left out, so
+// elements absent from every set cost nothing. Smaller elements are tested first.
+pub struct Manager {
+    nodes: Vec<Entry>,
+    unique: HashMap<Entry, Family>,
+    cache: HashMap<(Operation, Family, Family), Family>,
+}
+
+impl Default for Manager {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Manager {
+    pub fn new() -> Self {
+        let terminal = Entry {
+            element: i64::MAX,
+            low: Family::EMPTY,
+            high: Family::EMPTY,
+        };
+
+        Self {
+            nodes: vec![terminal, terminal],
+            unique: HashMap::new(),
+            cache: HashMap::new(),
+        }
+    }
+
+    // The family holding only `set`
+    pub fn singleton(&mut self, set: &[i32]) -> Family {
+        let mut elements = set.to_vec();
+
+        elements.sort();
+        elements.dedup();
+        elements
+            .into_iter()
+            .rev()
+            .fold(Family::BASE, |f, e| self.make(e.into(), Family::EMPTY, f))
+    }
+
+    pub fn from_sets<'a>(&mut self, sets: impl IntoIterator<Item = &'a [i32]>) -> Family {
+        sets.into_iter().fold(Family::EMPTY, |f, set| {
+            let g = self.singleton(set);
+
+            self.union(f, g)
+        })
+    }
+
+    // Every subset of `set`, in a node per element
+    pub fn powerset(&mut self, set: &[i32]) -> Family {
+        let mut elements = set.to_vec();
+
+        elements.sort();
+        elements.dedup();
+        elements
+            .into_iter()
+            .rev()
+            .fold(Family::BASE, |f, e| self.make(e.into(), f, f))
+    }
+
+    pub fn union(&mut self, f: Family, g: Family) -> Family {
+        match (f, g) {
+            (Family::EMPTY, _) => g,
+            (_, Family::EMPTY) => f,
+            _ if f == g => f,
+            _ => self.apply(Operation::Union, f.min(g), f.max(g)),
+        }
+    }
+
+    pub fn intersection(&mut self, f: Family, g: Family) -> Family {
+        match (f, g) {
+            (Family::EMPTY, _) | (_, Family::EMPTY) => Family::EMPTY,
+            _ if f == g => f,
+            _ => self.apply(Operation::Intersection, f.min(g), f.max(g)),
+        }
+    }
+
+    // The sets of f that are not in g
+    pub fn difference(&mut self, f: Family, g: Family) -> Family {
+        match (f, g) {
+            (Family::EMPTY, _) => Family::EMPTY,
+            (_, Family::EMPTY) => f,
+            _ if f == g => Family::EMPTY,
+            _ => self.apply(Operation::Difference, f, g),
+        }
+    }
+
+    // a ∪ b for every a of f and b of g
+    pub fn join(&mut self, f: Family, g: Family) -> Family {
+        match (f, g) {
+            (Family::EMPTY, _) | (_, Family::EMPTY) => Family::EMPTY,
+            (Family::BASE, _) => g,
+            (_, Family::BASE) => f,
+            _ => self.apply(Operation::Join, f.min(g), f.max(g)),
+        }
+    }
+
+    // The sets of f within `set`
+    pub fn subsets(&mut self, f: Family, set: &[i32]) -> Family {
+        let power = self.powerset(set);
+
+        self.intersection(f, power)
+    }
+
+    // The sets of f around `set`
+    pub fn supersets(&mut self, f: Family, set: &[i32]) -> Family {
+        set.iter()
+            .fold(f, |f, &e| self.containing(f, e.into(), &mut HashMap::new()))
+    }
+
+    pub fn contains(&self, f: Family, set: &[i32]) -> bool {
+        let mut elements = set.to_vec();
+        let mut f = f;
+
+        elements.sort();
+        elements.dedup();
+
+        for e in elements.into_iter().map(i64::from) {
+            while self.element(f) < e {
+                f = self.nodes[f.index()].low;
+            }
+
+            match self.element(f) == e {
+                true => f = self.nodes[f.index()].high,
+                false => return false,
+            }
+        }
+
+        while !f.is_constant() {
+            f = self.nodes[f.index()].low;
+        }
+
+        f == Family::BASE
+    }
+
+    // The number of sets, None past u128
+    pub fn count(&self, f: Family) -> Option<u128> {
+        self.count_memo(f, &mut HashMap::new())
+    }
+
+    // Nodes of the diagram, the terminals included
+    pub fn size(&self, f: Family) -> usize {
+        let mut seen = vec![false; self.nodes.len()];
+        let mut stack = vec![f];
+        let mut size = 0;
+
+        while let Some(f) = stack.pop() {
+            if std::mem::replace(&mut seen[f.index()], true) {
+                continue;
+            }
+
+            size += 1;
+
+            if !f.is_constant() {
+                let entry = self.nodes[f.index()];
+
+                stack.extend([entry.low, entry.high]);
+            }
+        }
+
+        size
+    }
+
+    // Each set sorted, sets without an element before those with it
+    pub fn iter(&self, f: Family) -> Sets<'_> {
+        Sets {
+            manager: self,
+            stack: vec![(f, Vec::new())],
+        }
+    }
+
+    fn make(&mut self, element: i64, low: Family, high: Family) -> Family {
+        if high == Family::EMPTY {
+            return low;
+        }
+
+        let entry = Entry { element, low, high };
+
+        *self.unique.entry(entry).or_insert_with(|| {
+            self.nodes.push(entry);
+            Family(self.nodes.len() as u32 - 1)
+        })
+    }
+
+    fn element(&self, f: Family) -> i64 {
+        self.nodes[f.index()].element
+    }
+
+    // The sets without and with `element`, taken out
+    fn cofactors(&self, f: Family, element: i64) -> (Family, Family) {
+        let entry = self.nodes[f.index()];
+
+        match entry.element == element {
+            true => (entry.low, entry.high),
+            false => (f, Family::EMPTY),
+        }
+    }
+
+    fn apply(&mut self, operation: Operation, f: Family, g: Family) -> Family {
+        if let Some(&r) = self.cache.get(&(operation, f, g)) {
+            return r;
+        }
+
+        let element = self.element(f).min(self.element(g));
+        let (f0, f1) = self.cofactors(f, element);
+        let (g0, g1) = self.cofactors(g, element);
+
+        let r = match operation {
+            Operation::Union => {
+                let low = self.union(f0, g0);
+                let high = self.union(f1, g1);
+
+                self.make(element, low, high)
+            }
+            Operation::Intersection => {
+                let low = self.intersection(f0, g0);
+                let high = self.intersection(f1, g1);
+
+                self.make(element, low, high)
+            }
+            Operation::Difference => {
+                let low = self.difference(f0, g0);
+                let high = self.difference(f1, g1);
+
+                self.make(element, low, high)
+            }
+            // (f0 + e·f1)(g0 + e·g1) = f0·g0 + e·(f1·g1 + f1·g0 + f0·g1)
+            Operation::Join => {
+                let low = self.join(f0, g0);
+                let both = self.join(f1, g1);
+                let left = self.join(f1, g0);
+                let right = self.join(f0, g1);
+                let high = self.union(both, left);
+                let high = self.union(high, right);
+
+                self.make(element, low, high)
+            }
+        };
+
+        self.cache.insert((operation, f, g), r);
+        r
+    }
+
+    fn containing(
+        &mut self,
+        f: Family,
+        element: i64,
+        memo: &mut HashMap<Family, Family>,
+    ) -> Family {
+        if self.element(f) > element {
+            return Family::EMPTY;
+        }
+
+        if let Some(&r) = memo.get(&f) {
+            return r;
+        }
+
+        let entry = self.nodes[f.index()];
+        let r = match entry.element == element {
+            true => self.make(element, Family::EMPTY, entry.high),
+            false => {
+                let low = self.containing(entry.low, element, memo);
+                let high = self.containing(entry.high, element, memo);
+
+                self.make(entry.element, low, high)
+            }
+        };
+
+        memo.insert(f, r);
+        r
+    }
+
+    fn count_memo(&self, f: Family, memo: &mut HashMap<Family, u128>) -> Option<u128> {
+        if f.is_constant() {
+            return Some((f == Family::BASE) as u128);
+        }
+
+        if let Some(&count) = memo.get(&f) {
+            return Some(count);
+        }
+
+        let entry = self.nodes[f.index()];
+        let count = self
+            .count_memo(entry.low, memo)?
+            .checked_add(self.count_memo(entry.high, memo)?)?;
+
+        memo.insert(f, count);
+        Some(count)
+    }
+}
+
+// Walks the diagram with an explicit stack, one set at a time
+pub struct Sets<'a> {
+    manager: &'a Manager,
+    stack: Vec<(Family, Vec<i32>)>,
+}
+
+impl Iterator for Sets<'_> {
+    type Item = Vec<i32>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        while let Some((f, set)) = self.stack.pop() {
+            match f {
+                Family::EMPTY => (),
+                Family::BASE => return Some(set),
+                _ => {
+                    let entry = self.manager.nodes[f.index()];
+                    let mut with = set.clone();
+
+                    with.push(entry.element as i32);
+                    self.stack.push((entry.high, with));
+                    self.stack.push((entry.low, set));
+                }
+            }
+        }
+
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::set::powerset;
+
+    #[test]
+    fn algebra() {
+        let mut manager = Manager::new();
+        let f = manager.from_sets([&[1, 2][..], &[3], &[]]);
+        let g = manager.from_sets([&[2, 1][..], &[4]]);
+
+        let union = manager.union(f, g);
+        compare(&manager, union, &[vec![], vec![1, 2], vec![3], vec![4]]);
+
+        let intersection = manager.intersection(f, g);
+        compare(&manager, intersection, &[vec![1, 2]]);
+
+        let difference = manager.difference(f, g);
+        compare(&manager, difference, &[vec![], vec![3]]);
+
+        let join = manager.join(f, g);
+        compare(
+            &manager,
+            join,
+            &[
+                vec![1, 2],
+                vec![1, 2, 3],
+                vec![1, 2, 4],
+                vec![3, 4],
+                vec![4],
+            ],
+        );
+
+        assert!(manager.contains(f, &[2, 1]));
+        assert!(manager.contains(f, &[]));
+        assert!(!manager.contains(f, &[1]));
+        assert!(!manager.contains(g, &[]));
+    }
+
+    #[test]
+    fn filters() {
+        let mut manager = Manager::new();
+        let f = manager.from_sets([&[1, 2][..], &[1, 3], &[2], &[1, 2, 3], &[]]);
+
+        let subsets = manager.subsets(f, &[1, 2]);
+        compare(&manager, subsets, &[vec![], vec![1, 2], vec![2]]);
+
+        let supersets = manager.supersets(f, &[1]);
+        compare(
+            &manager,
+            supersets,
+            &[vec![1, 2], vec![1, 2, 3], vec![1, 3]],
+        );
+
+        let supersets = manager.supersets(f, &[2, 3]);
+        compare(&manager, supersets, &[vec![1, 2, 3]]);
+    }
+
+    #[test]
+    fn powersets() {
+        let mut manager = Manager::new();
+        let set = [3, -1, 4, 10];
+        let f = manager.powerset(&set);
+        let mut expected = powerset(set.to_vec());
+
+        expected.iter_mut().for_each(|set| set.sort());
+        compare(&manager, f, &expected);
+
+        // Far past anything a Vec of subsets could hold
+        let elements: Vec<i32> = (0..60).collect();
+        let f = manager.powerset(&elements);
+
+        assert_eq!(manager.count(f), Some(1 << 60));
+        assert_eq!(manager.size(f), 60 + 1);
+
+        let g = manager.supersets(f, &[0, 59]);
+
+        assert_eq!(manager.count(g), Some(1 << 58));
+
+        let h = manager.subsets(g, &[0, 1, 59]);
+
+        assert_eq!(manager.count(h), Some(2));
+
+        let singletons = manager.from_sets(elements.iter().map(std::slice::from_ref));
+        let pairs = manager.join(singletons, singletons);
+        let pairs = manager.difference(pairs, singletons);
+
+        assert_eq!(manager.count(pairs), Some(60 * 59 / 2));
+        assert_eq!(manager.iter(pairs).next(), Some(vec![58, 59]));
+
+        // 2^128 sets no longer fit, 2^127 still do
+        let elements: Vec<i32> = (0..128).collect();
+        let f = manager.powerset(&elements);
+
+        let g = manager.supersets(f, &[0]);
+
+        assert_eq!(manager.count(f), None);
+        assert_eq!(manager.count(g), Some(1 << 127));
+    }
+
+    fn compare(manager: &Manager, f: Family, expected: &[Vec<i32>]) {
+        let mut sets: Vec<Vec<i32>> = manager.iter(f).collect();
+        let mut expected = expected.to_vec();
+
+        sets.sort();
+        expected.sort();
+
+        assert_eq!(sets, expected);
+        assert_eq!(manager.count(f), Some(expected.len() as u128));
+    }
+}
@@ -0,0 +1,612 @@
+use std::{mem, ops::Not};
+
+// Variable index and sign packed together, so a literal indexes its watch list
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Literal(u32);
+
+impl Literal {
+    // Variables a literal can tell apart, one bit of its index being the sign
+    pub const VARIABLES: usize = 1 << 31;
+
+    pub fn new(variable: usize, positive: bool) -> Self {
+        Self::try_new(variable, positive).expect("variable index past Literal::VARIABLES")
+    }
+
+    pub fn try_new(variable: usize, positive: bool) -> Option<Self> {
+        let variable = u32::try_from(variable).ok().filter(|&v| v < 1 << 31)?;
+
+        Some(Self(variable << 1 | !positive as u32))
+    }
+
+    pub fn variable(self) -> usize {
+        (self.0 >> 1) as usize
+    }
+
+    pub fn is_positive(self) -> bool {
+        self.0 & 1 == 0
+    }
+
+    fn index(self) -> usize {
+        self.0 as usize
+    }
+}
+
+impl Not for Literal {
+    type Output = Self;
+
+    fn not(self) -> Self {
+        Self(self.0 ^ 1)
+    }
+}
+
+// A conjunction of disjunctions over variables 0..variables
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Cnf {
+    variables: usize,
+    clauses: Vec<Vec<Literal>>,
+}
+
+impl Cnf {
+    pub fn new(variables: usize) -> Self {
+        Self {
+            variables,
+            clauses: Vec::new(),
+        }
+    }
+
+    pub fn variables(&self) -> usize {
+        self.variables
+    }
+
+    pub fn clauses(&self) -> &[Vec<Literal>] {
+        &self.clauses
+    }
+
+    pub fn fresh(&mut self) -> usize {
+        self.variables += 1;
+        self.variables - 1
+    }
+
+    // Duplicate literals are dropped, as are clauses that always hold
+    pub fn add_clause(&mut self, mut clause: Vec<Literal>) {
+        clause.sort();
+        clause.dedup();
+
+        if clause.windows(2).any(|w| w[0] == !w[1]) {
+            return;
+        }
+
+        for literal in clause.iter() {
+            self.variables = self.variables.max(literal.variable() + 1);
+        }
+
+        self.clauses.push(clause);
+    }
+
+    pub fn evaluate(&self, model: &[bool]) -> bool {
+        self.clauses.iter().all(|clause| {
+            clause
+                .iter()
+                .any(|literal| model[literal.variable()] == literal.is_positive())
+        })
+    }
+
+    // A value for every variable that satisfies all clauses, if there is one
+    pub fn solve(&self) -> Option<Vec<bool>> {
+        Solver::new(self.variables).solve(&self.clauses)
+    }
+}
+
+const RESTART: usize = 100;
+const DECAY: f64 = 0.95;
+
+// Conflict-driven clause learning: two watched literals per clause,
+// first-UIP learning, non-chronological backjumps, Luby restarts and VSIDS
+struct Solver {
+    clauses: Vec<Vec<Literal>>,
+    // Per literal, the clauses that watch it
+    watches: Vec<Vec<usize>>,
+
+    values: Vec<Option<bool>>,
+    levels: Vec<usize>,
+    reasons: Vec<Option<usize>>,
+    // Last value of every variable, reused when branching on it again
+    phases: Vec<bool>,
+
+    trail: Vec<Literal>,
+    // Where every decision level starts on the trail
+    decisions: Vec<usize>,
+    head: usize,
+
+    activity: Vec<f64>,
+    increment: f64,
+    order: Heap,
+}
+
+impl Solver {
+    fn new(variables: usize) -> Self {
+        let mut order = Heap::new(variables);
+        let activity = vec![0.0; variables];
+
+        for v in 0..variables {
+            order.insert(v, &activity);
+        }
+
+        Self {
+            clauses: Vec::new(),
+            watches: vec![Vec::new(); variables * 2],
+            values: vec![None; variables],
+            levels: vec![0; variables],
+            reasons: vec![None; variables],
+            phases: vec![false; variables],
+            trail: Vec::new(),
+            decisions: Vec::new(),
+            head: 0,
+            activity,
+            increment: 1.0,
+            order,
+        }
+    }
+
+    fn solve(mut self, clauses: &[Vec<Literal>]) -> Option<Vec<bool>> {
+        for clause in clauses {
+            match clause[..] {
+                [] => return None,
+                [literal] => match self.value(literal) {
+                    Some(false) => return None,
+                    Some(true) => (),
+                    None => self.assign(literal, None),
+                },
+                _ => {
+                    self.learn(clause.clone());
+                }
+            }
+        }
+
+        let mut restarts = 0;
+        let mut conflicts = 0;
+
+        loop {
+            if let Some(conflict) = self.propagate() {
+                if self.decisions.is_empty() {
+                    return None;
+                }
+
+                let (learnt, level) = self.analyze(conflict);
+
+                self.backtrack(level);
+
+                match learnt.len() {
+                    1 => self.assign(learnt[0], None),
+                    _ => {
+                        let literal = learnt[0];
+                        let reason = self.learn(learnt);
+
+                        self.assign(literal, Some(reason));
+                    }
+                }
+
+                self.increment /= DECAY;
+                conflicts += 1;
+            } else if conflicts >= luby(restarts) * RESTART {
+                self.backtrack(0);
+                restarts += 1;
+                conflicts = 0;
+            } else {
+                let Some(variable) = self.branch() else {
+                    return Some(self.values.into_iter().map(Option::unwrap).collect());
+                };
+
+                self.decisions.push(self.trail.len());
+                self.assign(Literal::new(variable, self.phases[variable]), None);
+            }
+        }
+    }
+
+    fn value(&self, literal: Literal) -> Option<bool> {
+        value(&self.values, literal)
+    }
+
+    fn assign(&mut self, literal: Literal, reason: Option<usize>) {
+        let v = literal.variable();
+
+        self.values[v] = Some(literal.is_positive());
+        self.levels[v] = self.decisions.len();
+        self.reasons[v] = reason;
+        self.trail.push(literal);
+    }
+
+    // Watches the first two literals, which must not be false
+    fn learn(&mut self, clause: Vec<Literal>) -> usize {
+        let index = self.clauses.len();
+
+        self.watches[clause[0].index()].push(index);
+        self.watches[clause[1].index()].push(index);
+        self.clauses.push(clause);
+
+        index
+    }
+
+    // Returns the conflicting clause, if any
+    fn propagate(&mut self) -> Option<usize> {
+        while self.head < self.trail.len() {
+            let falsified = !self.trail[self.head];
+            let mut watching = mem::take(&mut self.watches[falsified.index()]);
+            let mut i = 0;
+
+            self.head += 1;
+
+            while i < watching.len() {
+                let index = watching[i];
+                let clause = &mut self.clauses[index];
+                let values = &self.values;
+
+                // Keep the falsified watch in the second position
+                if clause[0] == falsified {
+                    clause.swap(0, 1);
+                }
+
+                let first = clause[0];
+
+                if value(values, first) == Some(true) {
+                    i += 1;
+                    continue;
+                }
+
+                if let Some(k) =
+                    (2..clause.len()).find(|&k| value(values, clause[k]) != Some(false))
+                {
+                    clause.swap(1, k);
+                    self.watches[clause[1].index()].push(index);
+                    watching.swap_remove(i);
+                    continue;
+                }
+
+                if self.value(first) == Some(false) {
+                    self.watches[falsified.index()] = watching;
+                    self.head = self.trail.len();
+
+                    return Some(index);
+                }
+
+                self.assign(first, Some(index));
+                i += 1;
+            }
+
+            self.watches[falsified.index()] = watching;
+        }
+
+        None
+    }
+
+    // First unique implication point: the learnt clause and the level to jump back to
+    fn analyze(&mut self, conflict: usize) -> (Vec<Literal>, usize) {
+        let level = self.decisions.len();
+        let mut learnt = vec![Literal(0)];
+        let mut seen = vec![false; self.values.len()];
+        let mut pending = 0;
+        let mut index = self.trail.len();
+        let mut reason = conflict;
+        let mut skip = 0;
+
+        loop {
+            for k in skip..self.clauses[reason].len() {
+                let literal = self.clauses[reason][k];
+                let v = literal.variable();
+
+                if seen[v] || self.levels[v] == 0 {
+                    continue;
+                }
+
+                seen[v] = true;
+                self.bump(v);
+
+                match self.levels[v] == level {
+                    true => pending += 1,
+                    false => learnt.push(literal),
+                }
+            }
+
+            // The most recent literal on the trail that led here
+            loop {
+                index -= 1;
+
+                if seen[self.trail[index].variable()] {
+                    break;
+                }
+            }
+
+            let literal = self.trail[index];
+
+            seen[literal.variable()] = false;
+            pending -= 1;
+
+            if pending == 0 {
+                learnt[0] = !literal;
+                break;
+            }
+
+            reason = self.reasons[literal.variable()].unwrap();
+            // The implied literal itself comes first in its reason
+            skip = 1;
+        }
+
+        // The deepest of the other literals is watched, it is the first to become unassigned
+        let backjump = match (1..learnt.len()).max_by_key(|&k| self.levels[learnt[k].variable()]) {
+            Some(k) => {
+                learnt.swap(1, k);
+                self.levels[learnt[1].variable()]
+            }
+            None => 0,
+        };
+
+        (learnt, backjump)
+    }
+
+    fn backtrack(&mut self, level: usize) {
+        if self.decisions.len() <= level {
+            return;
+        }
+
+        for literal in self.trail.drain(self.decisions[level]..).rev() {
+            let v = literal.variable();
+
+            self.values[v] = None;
+            self.reasons[v] = None;
+            self.phases[v] = literal.is_positive();
+            self.order.insert(v, &self.activity);
+        }
+
+        self.decisions.truncate(level);
+        self.head = self.trail.len();
+    }
+
+    // The unassigned variable with the highest activity
+    fn branch(&mut self) -> Option<usize> {
+        while let Some(v) = self.order.pop(&self.activity) {
+            if self.values[v].is_none() {
+                return Some(v);
+            }
+        }
+
+        None
+    }
+
+    fn bump(&mut self, v: usize) {
+        self.activity[v] += self.increment;
+
+        if self.activity[v] > 1e100 {
+            for activity in self.activity.iter_mut() {
+                *activity *= 1e-100;
+            }
+
+            self.increment *= 1e-100;
+        }
+
+        self.order.increase(v, &self.activity);
+    }
+}
+
+fn value(values: &[Option<bool>], literal: Literal) -> Option<bool> {
+    values[literal.variable()].map(|b| b == literal.is_positive())
+}
+
+// 1, 1, 2, 1, 1, 2, 4, 1, 1, 2, 1, 1, 2, 4, 8, ...
+fn luby(mut i: usize) -> usize {
+    let mut size = 1;
+    let mut sequence = 0;
+
+    while size < i + 1 {
+        sequence += 1;
+        size = 2 * size + 1;
+    }
+
+    while size - 1 != i {
+        size = (size - 1) >> 1;
+        sequence -= 1;
+        i %= size;
+    }
+
+    1 << sequence
+}
+
+// Binary max-heap of variables ordered by activity
+struct Heap {
+    heap: Vec<usize>,
+    positions: Vec<Option<usize>>,
+}
+
+impl Heap {
+    fn new(variables: usize) -> Self {
+        Self {
+            heap: Vec::with_capacity(variables),
+            positions: vec![None; variables],
+        }
+    }
+
+    fn insert(&mut self, v: usize, activity: &[f64]) {
+        if self.positions[v].is_none() {
+            self.positions[v] = Some(self.heap.len());
+            self.heap.push(v);
+            self.up(self.heap.len() - 1, activity);
+        }
+    }
+
+    fn increase(&mut self, v: usize, activity: &[f64]) {
+        if let Some(i) = self.positions[v] {
+            self.up(i, activity);
+        }
+    }
+
+    fn pop(&mut self, activity: &[f64]) -> Option<usize> {
+        let top = *self.heap.first()?;
+        let last = self.heap.pop().unwrap();
+
+        self.positions[top] = None;
+
+        if !self.heap.is_empty() {
+            self.heap[0] = last;
+            self.positions[last] = Some(0);
+            self.down(0, activity);
+        }
+
+        Some(top)
+    }
+
+    fn up(&mut self, mut i: usize, activity: &[f64]) {
+        while i > 0 {
+            let parent = (i - 1) / 2;
+
+            if activity[self.heap[parent]] >= activity[self.heap[i]] {
+                break;
+            }
+
+            self.swap(i, parent);
+            i = parent;
+        }
+    }
+
+    fn down(&mut self, mut i: usize, activity: &[f64]) {
+        loop {
+            let mut largest = i;
+
+            for child in [2 * i + 1, 2 * i + 2] {
+                if child < self.heap.len()
+                    && activity[self.heap[child]] > activity[self.heap[largest]]
+                {
+                    largest = child;
+                }
+            }
+
+            if largest == i {
+                break;
+            }
+
+            self.swap(i, largest);
+            i = largest;
+        }
+    }
+
+    fn swap(&mut self, i: usize, j: usize) {
+        self.heap.swap(i, j);
+        self.positions[self.heap[i]] = Some(i);
+        self.positions[self.heap[j]] = Some(j);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::Rng;
+
+    const N: usize = 200;
+
+    #[test]
+    fn small() {
+        let a = Literal::new(0, true);
+        let b = Literal::new(1, true);
+
+        assert!(solve(2, vec![vec![a, b], vec![!a], vec![!b, a]]).is_none());
+        assert!(solve(2, vec![vec![a, b], vec![!a, b], vec![!b, a]]).is_some());
+        assert!(solve(1, vec![vec![]]).is_none());
+        assert!(solve(0, vec![]).is_some());
+    }
+
+    #[test]
+    fn luby() {
+        let sequence: Vec<usize> = (0..15).map(super::luby).collect();
+
+        assert_eq!(sequence, [1, 1, 2, 1, 1, 2, 4, 1, 1, 2, 1, 1, 2, 4, 8]);
+    }
+
+    #[test]
+    fn literals() {
+        let last = Literal::try_new(Literal::VARIABLES - 1, false).unwrap();
+
+        assert_eq!(last.variable(), Literal::VARIABLES - 1);
+        assert!(!last.is_positive());
+        assert_eq!(Literal::try_new(Literal::VARIABLES, true), None);
+        assert_eq!(Literal::try_new(1 << 32, true), None);
+    }
+
+    // n + 1 pigeons don't fit in n holes
+    #[test]
+    fn pigeonhole() {
+        let n = 6;
+        let hole = |p: usize, h: usize| Literal::new(p * n + h, true);
+        let mut clauses = Vec::new();
+
+        for p in 0..=n {
+            clauses.push((0..n).map(|h| hole(p, h)).collect());
+        }
+
+        for h in 0..n {
+            for p in 0..=n {
+                for q in p + 1..=n {
+                    clauses.push(vec![!hole(p, h), !hole(q, h)]);
+                }
+            }
+        }
+
+        assert!(solve((n + 1) * n, clauses).is_none());
+    }
+
+    // Far past what enumerating assignments could handle
+    #[test]
+    fn chain() {
+        let n = 5000;
+        let mut clauses = vec![vec![Literal::new(0, true)]];
+
+        for v in 1..n {
+            clauses.push(vec![Literal::new(v - 1, false), Literal::new(v, true)]);
+        }
+
+        assert_eq!(solve(n, clauses.clone()), Some(vec![true; n]));
+
+        clauses.push(vec![Literal::new(n - 1, false)]);
+
+        assert!(solve(n, clauses).is_none());
+    }
+
+    #[test]
+    fn rand() {
+        let mut rng = rand::rng();
+
+        for _ in 0..N {
+            let variables = rng.random_range(1..=10);
+            let clauses: Vec<Vec<Literal>> = (0..rng.random_range(0..=45))
+                .map(|_| {
+                    (0..3)
+                        .map(|_| Literal::new(rng.random_range(0..variables), rng.random()))
+                        .collect()
+                })
+                .collect();
+
+            let brute = (0..1 << variables).any(|i| {
+                clauses.iter().all(|clause| {
+                    clause
+                        .iter()
+                        .any(|l| (i >> l.variable() & 1 == 1) == l.is_positive())
+                })
+            });
+
+            assert_eq!(solve(variables, clauses).is_some(), brute);
+        }
+    }
+
+    fn solve(variables: usize, clauses: Vec<Vec<Literal>>) -> Option<Vec<bool>> {
+        let mut cnf = Cnf::new(variables);
+
+        for clause in clauses {
+            cnf.add_clause(clause);
+        }
+
+        let model = cnf.solve();
+
+        if let Some(model) = &model {
+            assert!(cnf.evaluate(model));
+        }
+
+        model
+    }
+}